@@ -0,0 +1,329 @@
+use std::fmt::{self, Display};
+
+use num_bigint::BigInt;
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// What a solution produces
+///
+/// Integers that fit in an `i64` are always stored as `Integer`, so two answers with the
+/// same value compare equal no matter which type the solution computed them with
+///
+/// Serialized as a number, a string of digits for big integers (which JSON readers can't be
+/// trusted with), a string for text, and the letters and rows of a grid
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(BigInt),
+    Text(String),
+    /// Letters drawn with lit cells, e.g. the folded paper of day 13
+    Grid(Grid),
+}
+
+impl Answer {
+    /// The answer as it would be typed into the website. Grids are read with `Grid::ocr`,
+    /// and fall back to their rendering if they can't be read
+    pub fn to_submission(&self) -> String {
+        match self {
+            Answer::Grid(grid) => grid.ocr().unwrap_or_else(|| grid.to_string()),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::BigInteger(n) => write!(f, "{}", n),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Grid(grid) => write!(f, "{}", grid),
+        }
+    }
+}
+
+macro_rules! impl_from_small_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Integer(i64::from(n))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_large_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    match i64::try_from(n) {
+                        Ok(n) => Answer::Integer(n),
+                        Err(_) => Answer::BigInteger(BigInt::from(n)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_small_integer!(i8, i16, i32, i64, u8, u16, u32);
+impl_from_large_integer!(u64, usize, i128, u128);
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        match i64::try_from(&n) {
+            Ok(n) => Answer::Integer(n),
+            Err(_) => Answer::BigInteger(n),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl From<Grid> for Answer {
+    fn from(grid: Grid) -> Self {
+        Answer::Grid(grid)
+    }
+}
+
+/// A rectangle of lit and unlit cells
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    cells: Vec<bool>,
+}
+
+/// Width and height of a letter, as drawn by the puzzles
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+
+const GLYPHS: [(char, [&str; GLYPH_HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...", "#...", ".#.#", "..#.", "..#.", "..#."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+impl Grid {
+    pub fn new(width: usize, height: usize) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![false; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Out of bounds cells are unlit
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.cells[x + self.width * y]
+    }
+
+    /// Lights up a cell. Out of bounds cells are ignored
+    pub fn set(&mut self, x: usize, y: usize) {
+        if x < self.width && y < self.height {
+            self.cells[x + self.width * y] = true;
+        }
+    }
+
+    /// Reads the letters drawn in the grid, if they're all recognised
+    ///
+    /// Letters are 4x6 and separated by one empty column, starting at the left edge.
+    /// Empty rows below the letters are ignored
+    pub fn ocr(&self) -> Option<String> {
+        let height = (0..self.height)
+            .rev()
+            .find(|&y| (0..self.width).any(|x| self.get(x, y)))
+            .map(|y| y + 1)?;
+        if height != GLYPH_HEIGHT {
+            return None;
+        }
+        let mut letters = String::new();
+        for left in (0..self.width).step_by(GLYPH_WIDTH + 1) {
+            let glyph = (0..GLYPH_HEIGHT)
+                .map(|y| {
+                    (left..left + GLYPH_WIDTH)
+                        .map(|x| if self.get(x, y) { '#' } else { '.' })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>();
+            if glyph.iter().all(|row| row == "....") {
+                continue;
+            }
+            let (letter, _) = GLYPHS.iter().find(|(_, rows)| rows[..] == glyph[..])?;
+            letters.push(*letter);
+        }
+        Some(letters)
+    }
+}
+
+impl Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..self.width {
+                write!(f, "{}", if self.get(x, y) { '#' } else { ' ' })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Integer(n) => serializer.serialize_i64(*n),
+            Answer::BigInteger(n) => serializer.collect_str(n),
+            Answer::Text(text) => serializer.serialize_str(text),
+            Answer::Grid(grid) => grid.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+struct AnswerVisitor;
+
+impl<'de> Visitor<'de> for AnswerVisitor {
+    type Value = Answer;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a number, a string or a grid")
+    }
+
+    fn visit_i64<E: de::Error>(self, n: i64) -> Result<Answer, E> {
+        Ok(n.into())
+    }
+
+    fn visit_u64<E: de::Error>(self, n: u64) -> Result<Answer, E> {
+        Ok(n.into())
+    }
+
+    fn visit_i128<E: de::Error>(self, n: i128) -> Result<Answer, E> {
+        Ok(n.into())
+    }
+
+    fn visit_u128<E: de::Error>(self, n: u128) -> Result<Answer, E> {
+        Ok(n.into())
+    }
+
+    /// Digits that don't fit in an `i64` are a big integer, anything else is text
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Answer, E> {
+        match text.parse::<BigInt>() {
+            Ok(n) if i64::try_from(&n).is_err() => Ok(Answer::BigInteger(n)),
+            _ => Ok(text.into()),
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Answer, A::Error> {
+        let grid = Grid::deserialize(de::value::MapAccessDeserializer::new(map))?;
+        Ok(grid.into())
+    }
+}
+
+/// Lit cells are `#` and unlit ones `.`, the same as the puzzles draw them
+impl Serialize for Grid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let rows = (0..self.height)
+            .map(|y| {
+                (0..self.width)
+                    .map(|x| if self.get(x, y) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        let mut grid = serializer.serialize_struct("Grid", 2)?;
+        grid.serialize_field("letters", &self.ocr())?;
+        grid.serialize_field("rows", &rows)?;
+        grid.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Grid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        /// The letters are read from the rows again, so they're left out
+        #[derive(Deserialize)]
+        struct Rows {
+            rows: Vec<String>,
+        }
+
+        let Rows { rows } = Rows::deserialize(deserializer)?;
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        let mut grid = Grid::new(width, rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            if row.chars().count() != width {
+                return Err(de::Error::custom(
+                    "every row of a grid needs to be as wide as the first",
+                ));
+            }
+            for (x, c) in row.chars().enumerate() {
+                if c == '#' {
+                    grid.set(x, y);
+                }
+            }
+        }
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_round_trip() {
+        let mut grid = Grid::new(2, 1);
+        grid.set(1, 0);
+        let answers = [
+            (Answer::from(-3), "-3"),
+            (Answer::from(u64::MAX), "\"18446744073709551615\""),
+            (Answer::from("42"), "\"42\""),
+            (Answer::from(grid), r#"{"letters":null,"rows":[".#"]}"#),
+        ];
+        for (answer, json) in answers {
+            assert_eq!(serde_json::to_string(&answer).unwrap(), json);
+            assert_eq!(serde_json::from_str::<Answer>(json).unwrap(), answer);
+        }
+    }
+}
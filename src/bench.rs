@@ -0,0 +1,277 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use structopt::StructOpt;
+use time::{Duration, Instant};
+
+use aoc2021::day::{Day, Part};
+use aoc2021::profile::Profile;
+use aoc2021::registry;
+
+use crate::verify::table;
+
+#[derive(StructOpt)]
+pub struct Bench {
+    /// Benchmark: time repeated runs and report statistics
+    #[structopt(long, short)]
+    pub bench: bool,
+    /// Untimed runs before benchmarking
+    #[structopt(long, default_value = "3")]
+    warmup: usize,
+    /// Timed runs when benchmarking
+    #[structopt(long, default_value = "10")]
+    iterations: usize,
+    /// Compare the benchmarks against the ones saved in this file
+    #[structopt(long)]
+    baseline: Option<PathBuf>,
+    /// Save the benchmarks into this file, to compare against later
+    #[structopt(long)]
+    save_baseline: Option<PathBuf>,
+}
+
+/// Summary of the timings of one phase, in microseconds
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    fn new(samples: &[Duration]) -> Self {
+        let mut samples = samples
+            .iter()
+            .map(|sample| sample.whole_nanoseconds() as f64 / 1_000.0)
+            .collect::<Vec<_>>();
+        samples.sort_by(f64::total_cmp);
+        let n = samples.len().max(1) as f64;
+        let median = match samples.len() {
+            0 => 0.0,
+            len if len % 2 == 0 => (samples[len / 2 - 1] + samples[len / 2]) / 2.0,
+            len => samples[len / 2],
+        };
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n;
+        // Nanosecond precision is plenty, and keeps saved baselines readable
+        let round = |micros: f64| (micros * 1_000.0).round() / 1_000.0;
+        Stats {
+            min: round(samples.first().copied().unwrap_or_default()),
+            median: round(median),
+            mean: round(mean),
+            stddev: round(variance.sqrt()),
+        }
+    }
+}
+
+/// Timings of reading the input, of parsing it and of solving it
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Benchmark {
+    pub read: Stats,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+impl Benchmark {
+    fn phases(&self) -> [(&'static str, &Stats); 3] {
+        [
+            ("read", &self.read),
+            ("parse", &self.parse),
+            ("solve", &self.solve),
+        ]
+    }
+}
+
+/// Benchmarks saved by `--save-baseline`, keyed by day and part
+struct Baseline {
+    path: PathBuf,
+    benchmarks: BTreeMap<String, Benchmark>,
+}
+
+impl Baseline {
+    /// A missing file is an empty baseline
+    fn open(path: &Path) -> Result<Self> {
+        let benchmarks = if path.exists() {
+            let contents = fs::read_to_string(path)
+                .wrap_err_with(|| format!("Could not read {}", path.display()))?;
+            toml::from_str(&contents)
+                .wrap_err_with(|| format!("Could not parse {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            benchmarks,
+        })
+    }
+
+    fn save(&self) -> Result<()> {
+        let contents = toml::to_string(&self.benchmarks)?;
+        fs::write(&self.path, contents)
+            .wrap_err_with(|| format!("Could not write {}", self.path.display()))
+    }
+}
+
+/// Variants other than the default one are benchmarked separately
+pub fn key(day: Day, part: Part, variant: &str) -> String {
+    let mut key = format!("{}-day{}-{}", day.year(), day.get(), part);
+    if variant != registry::DEFAULT_VARIANT {
+        key = format!("{}-{}", key, variant);
+    }
+    key
+}
+
+/// Microseconds, in whichever unit reads best
+fn format_micros(micros: f64) -> String {
+    if micros >= 1_000_000.0 {
+        format!("{:.2}s", micros / 1_000_000.0)
+    } else if micros >= 1_000.0 {
+        format!("{:.2}ms", micros / 1_000.0)
+    } else {
+        format!("{:.1}μs", micros)
+    }
+}
+
+impl Bench {
+    /// Reads, parses and solves the input `warmup` times, then `iterations` more times while
+    /// timing each phase
+    pub fn measure<P, T, E>(
+        &self,
+        read: impl Fn() -> Result<String>,
+        parse: impl Fn(&str) -> std::result::Result<P, E>,
+        solve: impl Fn(&P) -> std::result::Result<T, E>,
+    ) -> Result<Benchmark>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let mut reads = Vec::with_capacity(self.iterations);
+        let mut parses = Vec::with_capacity(self.iterations);
+        let mut solved = Vec::with_capacity(self.iterations);
+        for iteration in 0..self.warmup + self.iterations {
+            let start = Instant::now();
+            let input = read()?;
+            let read_end = Instant::now();
+            let parsed = black_box(parse(black_box(&input))?);
+            let parse_end = Instant::now();
+            let answer = black_box(solve(&parsed)?);
+            let solve_end = Instant::now();
+            if iteration >= self.warmup {
+                reads.push(read_end - start);
+                parses.push(parse_end - read_end);
+                solved.push(solve_end - parse_end);
+            }
+            drop((answer, parsed));
+        }
+        Ok(Benchmark {
+            read: Stats::new(&reads),
+            parse: Stats::new(&parses),
+            solve: Stats::new(&solved),
+        })
+    }
+
+    pub fn uses_baseline(&self) -> bool {
+        self.baseline.is_some() || self.save_baseline.is_some()
+    }
+
+    /// The benchmarks to compare against, if there's a baseline
+    pub fn baseline(&self) -> Result<Option<BTreeMap<String, Benchmark>>> {
+        match self.baseline {
+            Some(ref path) => Ok(Some(Baseline::open(path)?.benchmarks)),
+            None => Ok(None),
+        }
+    }
+
+    /// Adds `benchmarks` to the `--save-baseline` file, replacing older ones for the same key
+    pub fn save(&self, benchmarks: Vec<(String, Benchmark)>) -> Result<()> {
+        if let Some(ref path) = self.save_baseline {
+            let mut baseline = Baseline::open(path)?;
+            baseline.benchmarks.extend(benchmarks);
+            baseline.save()?;
+        }
+        Ok(())
+    }
+
+    /// A table of the statistics of each phase. With a baseline, the change of the median is
+    /// shown as a percentage, so a positive number is a regression
+    pub fn report(&self, benchmark: &Benchmark, baseline: Option<&Benchmark>) -> String {
+        let mut rows = vec![[
+            "Phase".to_string(),
+            "Min".to_string(),
+            "Median".to_string(),
+            "Mean".to_string(),
+            "Stddev".to_string(),
+            "Baseline".to_string(),
+        ]];
+        for (phase, stats) in benchmark.phases() {
+            let previous = baseline.and_then(|baseline| {
+                let phases = baseline.phases();
+                phases
+                    .iter()
+                    .find(|(name, _)| *name == phase)
+                    .map(|(_, s)| **s)
+            });
+            let change = match previous {
+                Some(previous) if previous.median > 0.0 => format!(
+                    "{:+.1}%",
+                    (stats.median - previous.median) / previous.median * 100.0
+                ),
+                _ => "-".to_string(),
+            };
+            rows.push([
+                phase.to_string(),
+                format_micros(stats.min),
+                format_micros(stats.median),
+                format_micros(stats.mean),
+                format_micros(stats.stddev),
+                change,
+            ]);
+        }
+        format!(
+            "{} iterations after {} warmup\n{}",
+            self.iterations,
+            self.warmup,
+            table(&rows)
+        )
+    }
+}
+
+/// The spans of `--profile` as an indented tree, with the share of its parent's time each
+/// one took
+pub fn profile_report(profile: &Profile) -> String {
+    let summary = profile.summary();
+    let micros = |duration: std::time::Duration| duration.as_nanos() as f64 / 1_000.0;
+    let top_level = summary
+        .iter()
+        .filter(|span| span.depth == 0)
+        .map(|span| micros(span.total))
+        .sum::<f64>();
+    let mut rows = vec![[
+        "Span".to_string(),
+        "Calls".to_string(),
+        "Total".to_string(),
+        "Share".to_string(),
+    ]];
+    // Totals of the spans the current one is nested in, by depth
+    let mut parents: Vec<f64> = vec![];
+    for span in &summary {
+        parents.truncate(span.depth);
+        let total = micros(span.total);
+        let parent = parents.last().copied().unwrap_or(top_level);
+        let share = match parent > 0.0 {
+            true => format!("{:.1}%", total / parent * 100.0),
+            false => "-".to_string(),
+        };
+        rows.push([
+            format!("{}{}", "  ".repeat(span.depth), span.name),
+            span.calls.to_string(),
+            format_micros(total),
+            share,
+        ]);
+        parents.push(total);
+    }
+    table(&rows)
+}
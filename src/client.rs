@@ -0,0 +1,192 @@
+use std::env;
+
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+
+use aoc2021::day::Day;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/ebenpack/aoc2021";
+
+/// How the website judged a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Right,
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying which way
+    Wrong,
+    /// Submitted too soon after the previous guess, the answer wasn't judged
+    RateLimited,
+    /// The part was already solved, or isn't unlocked yet
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the page returned after submitting
+    fn from_message(message: &str) -> Option<Self> {
+        if message.contains("That's the right answer") {
+            Some(Verdict::Right)
+        } else if message.contains("That's not the right answer") {
+            if message.contains("too high") {
+                Some(Verdict::TooHigh)
+            } else if message.contains("too low") {
+                Some(Verdict::TooLow)
+            } else {
+                Some(Verdict::Wrong)
+            }
+        } else if message.contains("You gave an answer too recently") {
+            Some(Verdict::RateLimited)
+        } else if message.contains("You don't seem to be solving the right level") {
+            Some(Verdict::WrongLevel)
+        } else {
+            None
+        }
+    }
+}
+
+/// The text of the `<article>` the website puts its response in
+fn article_text(body: &str) -> String {
+    let article = match (body.find("<article"), body.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &body[start..end],
+        _ => body,
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Talks to the Advent of Code website, or anything pretending to be it
+///
+/// Configured through the environment (or `.env`):
+/// `AOC_SESSION` is the value of the `session` cookie of a logged in browser, and
+/// `AOC_BASE_URL` points somewhere other than adventofcode.com, e.g. a local test server
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn from_env() -> Result<Self> {
+        let session = env::var("AOC_SESSION").wrap_err("AOC_SESSION is not set")?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, day.year(), day.get())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Downloads the puzzle input for `day`
+    pub fn input(&self, day: Day) -> Result<String> {
+        let url = format!("{}/input", self.day_url(day));
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie())
+            .call()
+            .map_err(|e| request_error(&url, e))?;
+        let input = response.into_string()?;
+        if input.trim().is_empty() {
+            bail!("{} returned an empty input", url);
+        }
+        Ok(input)
+    }
+
+    /// Submits `answer` for `part` of `day`, returning the verdict and the website's message
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<(Verdict, String)> {
+        let url = format!("{}/answer", self.day_url(day));
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| request_error(&url, e))?;
+        let message = article_text(&response.into_string()?);
+        match Verdict::from_message(&message) {
+            Some(verdict) => Ok((verdict, message)),
+            None => bail!("Could not make sense of the response: {}", message),
+        }
+    }
+}
+
+/// Turns HTTP errors into something more useful than a status code
+fn request_error(url: &str, error: ureq::Error) -> color_eyre::Report {
+    match error {
+        ureq::Error::Status(code, response) => {
+            let body = response.into_string().unwrap_or_default();
+            let reason = body.lines().next().unwrap_or_default().trim();
+            match code {
+                400 | 401 | 403 => eyre!("{} refused the session ({}): {}", url, code, reason),
+                404 => eyre!("{} not found, is the puzzle unlocked yet?", url),
+                _ => eyre!("{} failed ({}): {}", url, code, reason),
+            }
+        }
+        ureq::Error::Transport(transport) => eyre!("Could not reach {}: {}", url, transport),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verdict_from_message() {
+        let verdicts = [
+            (
+                "That's the right answer! You are one gold star closer to saving your vacation.",
+                Some(Verdict::Right),
+            ),
+            (
+                "That's not the right answer; your answer is too high. If you're stuck, \
+                 make sure you're using the full input data.",
+                Some(Verdict::TooHigh),
+            ),
+            (
+                "That's not the right answer; your answer is too low. Please wait one minute \
+                 before trying again.",
+                Some(Verdict::TooLow),
+            ),
+            (
+                "That's not the right answer. If you're stuck, make sure you're using the \
+                 full input data.",
+                Some(Verdict::Wrong),
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again. You have 34s left to wait.",
+                Some(Verdict::RateLimited),
+            ),
+            (
+                "You don't seem to be solving the right level. Did you already complete it?",
+                Some(Verdict::WrongLevel),
+            ),
+            ("Something else entirely", None),
+        ];
+        for (message, verdict) in verdicts {
+            assert_eq!(Verdict::from_message(message), verdict, "{}", message);
+        }
+    }
+}
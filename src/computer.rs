@@ -1,3 +1,5 @@
+use crate::error::{Error, Result};
+
 #[derive(Debug, Clone)]
 pub enum Operator {
     Sum,
@@ -35,7 +37,6 @@ enum PacketContents {
 
 #[derive(Debug, Clone)]
 pub struct Packet {
-    type_id: i64,
     version: i64,
     contents: PacketContents,
 }
@@ -111,73 +112,87 @@ pub struct Computer {
 }
 
 impl Computer {
-    pub fn load_input(input: &str) -> Self {
+    pub fn load_input(input: &str) -> Result<Self> {
         let mut code = vec![];
-        for line in input.lines() {
+        for (index, line) in input.lines().enumerate() {
             for char in line.chars() {
-                code.extend(
-                    format!(
-                        "{:04b}",
-                        i64::from_str_radix(&char.to_string(), 16).unwrap()
-                    )
-                    .chars(),
-                );
+                let nibble = char.to_digit(16).ok_or_else(|| {
+                    Error::parse(index + 1, line, format!("{:?} is not a hex digit", char))
+                })?;
+                code.extend(format!("{:04b}", nibble).chars());
             }
         }
-        Computer { code, index: 0 }
+        Ok(Computer { code, index: 0 })
     }
-    pub fn parse_packet(&mut self) -> Packet {
-        let version = self.parse_version();
-        let type_id = self.parse_type_id();
+    pub fn parse_packet(&mut self) -> Result<Packet> {
+        let version = self.parse_version()?;
+        let type_id = self.parse_type_id()?;
         match type_id {
             4 => {
-                let lit = self.parse_literal();
-                Packet {
-                    type_id,
+                let lit = self.parse_literal()?;
+                Ok(Packet {
                     version,
                     contents: PacketContents::Data(lit),
-                }
+                })
             }
             _ => {
-                let sub_packets = self.parse_operator();
-                Packet {
-                    type_id,
+                let operator = Operator::from_i64(type_id);
+                let sub_packets = self.parse_operator()?;
+                // Checked here so that `Packet::evaluate` can't fail
+                let arity_ok = match operator {
+                    Operator::GreaterThan | Operator::LessThan | Operator::Equal => {
+                        sub_packets.len() == 2
+                    }
+                    _ => !sub_packets.is_empty(),
+                };
+                if !arity_ok {
+                    return Err(Error::parse(
+                        1,
+                        "",
+                        format!(
+                            "{:?} packet ending at bit {} has {} sub-packets",
+                            operator,
+                            self.index,
+                            sub_packets.len()
+                        ),
+                    ));
+                }
+                Ok(Packet {
                     version,
                     contents: PacketContents::Operator {
-                        operator: Operator::from_i64(type_id),
+                        operator,
                         sub_packets,
                     },
-                }
+                })
             }
         }
     }
-    fn parse_literal(&mut self) -> i64 {
+    fn parse_literal(&mut self) -> Result<i64> {
         let mut num = vec![];
         loop {
-            let prefix = self.read_bits(1);
+            let prefix = self.read_bits(1)?;
             num.extend(
-                self.code[self.index..self.index + 4]
+                self.take(4)?
                     .iter()
                     .map(|c| c.to_owned()) // LOL
                     .collect::<Vec<char>>(),
             );
-            self.index += 4;
             if prefix == 0 {
                 break;
             }
         }
-        bit_char_arr_to_num(&num)
+        Ok(bit_char_arr_to_num(&num))
     }
-    fn parse_operator(&mut self) -> Vec<Packet> {
-        let length_type_id = self.read_bits(1);
+    fn parse_operator(&mut self) -> Result<Vec<Packet>> {
+        let length_type_id = self.read_bits(1)?;
         let mut length = None;
         let mut num_packets = None;
         match length_type_id {
             0 => {
-                length = Some(self.read_bits(15));
+                length = Some(self.read_bits(15)?);
             }
             1 => {
-                num_packets = Some(self.read_bits(11));
+                num_packets = Some(self.read_bits(11)?);
             }
             _ => unreachable!(),
         }
@@ -185,31 +200,37 @@ impl Computer {
             let start = self.index;
             let mut packets = vec![];
             while self.index < start + length as usize {
-                packets.push(self.parse_packet());
+                packets.push(self.parse_packet()?);
             }
-            packets
+            Ok(packets)
         } else if let Some(num_packets) = num_packets {
             let mut num_packets = num_packets;
             let mut packets = vec![];
             while num_packets > 0 {
-                packets.push(self.parse_packet());
+                packets.push(self.parse_packet()?);
                 num_packets -= 1;
             }
-            packets
+            Ok(packets)
         } else {
             unreachable!();
         }
     }
 
-    fn read_bits(&mut self, bits: usize) -> i64 {
-        let val = bit_char_arr_to_num(&self.code[self.index..self.index + bits]);
-        self.index += bits;
-        val
+    fn take(&mut self, bits: usize) -> Result<&[char]> {
+        let start = self.index;
+        let bits = self.code.get(start..start + bits).ok_or_else(|| {
+            Error::parse(1, "", format!("transmission ends early at bit {}", start))
+        })?;
+        self.index += bits.len();
+        Ok(bits)
+    }
+    fn read_bits(&mut self, bits: usize) -> Result<i64> {
+        Ok(bit_char_arr_to_num(self.take(bits)?))
     }
-    fn parse_version(&mut self) -> i64 {
+    fn parse_version(&mut self) -> Result<i64> {
         self.read_bits(3)
     }
-    fn parse_type_id(&mut self) -> i64 {
+    fn parse_type_id(&mut self) -> Result<i64> {
         self.read_bits(3)
     }
 }
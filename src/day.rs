@@ -1,62 +1,205 @@
+use std::collections::BTreeSet;
 use std::convert::TryInto;
+use std::fmt::{self, Display};
 use std::num::NonZeroU8;
 use std::str::FromStr;
 
-use aoc2021::*;
-use structopt::StructOpt;
+use crate::registry;
+use crate::AoCDay;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, StructOpt)]
+/// The year of an event. Commands that take a day take an optional year before it,
+/// e.g. `run 2021 16`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Year {
+    year: u16,
+}
+
+impl Year {
+    /// Used when no year is given, and for inputs that aren't filed under a year
+    pub const DEFAULT: Year = Year { year: 2021 };
+
+    /// Advent of Code started in 2015
+    pub fn new(year: u16) -> Result<Self, &'static str> {
+        if year < 2015 {
+            Err("Year needs to be 2015 or later")
+        } else {
+            Ok(Self { year })
+        }
+    }
+
+    pub fn get(&self) -> u16 {
+        self.year
+    }
+}
+
+impl Default for Year {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl FromStr for Year {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year: u16 = s.parse().map_err(|_| "Year needs to be an integer")?;
+        Self::new(year)
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.year)
+    }
+}
+
+/// One day of one event
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Day {
-    pub(crate) day: NonZeroU8,
+    year: Year,
+    day: NonZeroU8,
 }
 
+/// Parses the day alone, in the default year. Use `Day::in_year` to move it
 impl FromStr for Day {
     type Err = &'static str;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let day: u8 = s.parse().map_err(|_| "Day needs to be an integer")?;
+        Self::new(Year::DEFAULT, day)
+    }
+}
+
+/// A selection of days, as a comma separated list of days and ranges, e.g. `3,5,10-14`
+#[derive(Debug, Clone)]
+pub struct Days {
+    days: BTreeSet<u8>,
+}
+
+impl FromStr for Days {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = BTreeSet::new();
+        for item in s.split(',') {
+            match item.split_once('-') {
+                Some((start, end)) => {
+                    let start = start.trim().parse::<Day>()?.get();
+                    let end = end.trim().parse::<Day>()?.get();
+                    if start > end {
+                        return Err("Day ranges need to go from low to high, e.g. 10-14");
+                    }
+                    days.extend(start..=end);
+                }
+                None => {
+                    days.insert(item.trim().parse::<Day>()?.get());
+                }
+            }
+        }
+        Ok(Self { days })
+    }
+}
+
+impl Days {
+    /// In order, without duplicates
+    pub fn iter(&self, year: Year) -> impl Iterator<Item = Day> + '_ {
+        self.days.iter().map(move |&day| Day {
+            year,
+            day: day.try_into().unwrap(),
+        })
+    }
+}
+
+impl Day {
+    /// Days run from 1 to 25
+    pub fn new(year: Year, day: u8) -> Result<Self, &'static str> {
         if !(1..=25).contains(&day) {
             Err("Day value needs to be between 1 and 25 (inclusive)")
         } else {
             let day = day.try_into().unwrap();
-            Ok(Self { day })
+            Ok(Self { year, day })
         }
     }
-}
 
-impl Day {
     pub fn get(&self) -> u8 {
         self.day.get()
     }
 
-    pub fn get_code(&self) -> &dyn AoCDay {
-        match self.day.get() {
-            1 => &day1::Code,
-            2 => &day2::Code,
-            3 => &day3::Code,
-            4 => &day4::Code,
-            5 => &day5::Code,
-            6 => &day6::Code,
-            7 => &day7::Code,
-            8 => &day8::Code,
-            9 => &day9::Code,
-            10 => &day10::Code,
-            11 => &day11::Code,
-            12 => &day12::Code,
-            13 => &day13::Code,
-            14 => &day14::Code,
-            15 => &day15::Code,
-            16 => &day16::Code,
-            17 => &day17::Code,
-            18 => &day18::Code,
-            19 => &day19::Code,
-            20 => &day20::Code,
-            21 => &day21::Code,
-            22 => &day22::Code,
-            23 => &day23::Code,
-            24 => &day24::Code,
-            25 => &day25::Code,
-            _ => unreachable!(),
+    pub fn year(&self) -> Year {
+        self.year
+    }
+
+    /// The same day in `year`, if one was given
+    pub fn in_year(self, year: Option<Year>) -> Self {
+        match year {
+            Some(year) => Day { year, ..self },
+            None => self,
+        }
+    }
+
+    /// The default solution of the day
+    pub fn get_code(&self) -> &'static dyn AoCDay {
+        registry::get_default(self.year.get(), self.get())
+    }
+
+    /// A named solution of the day, `registry::DEFAULT_VARIANT` for the default one
+    pub fn get_variant(&self, variant: &str) -> Result<&'static dyn AoCDay, String> {
+        registry::get(self.year.get(), self.get(), variant).ok_or_else(|| {
+            let variants = registry::variants(self.year.get(), self.get());
+            if variants.is_empty() {
+                format!("{} day {} has no solutions", self.year, self.get())
+            } else {
+                format!(
+                    "{} day {} has no {:?} variant, it has: {}",
+                    self.year,
+                    self.get(),
+                    variant,
+                    variants.join(", ")
+                )
+            }
+        })
+    }
+}
+
+/// Which parts of a day to solve
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    Part1,
+    Part2,
+    #[default]
+    Both,
+}
+
+impl FromStr for Part {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "part1" | "1" => Ok(Self::Part1),
+            "part2" | "2" => Ok(Self::Part2),
+            "both" | "b" => Ok(Self::Both),
+            _ => Err("Unknown"),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::Part1 => write!(f, "part1"),
+            Part::Part2 => write!(f, "part2"),
+            Part::Both => write!(f, "both"),
+        }
+    }
+}
+
+impl Part {
+    /// The parts to run, by number
+    pub fn numbers(self) -> &'static [u8] {
+        match self {
+            Part::Part1 => &[1],
+            Part::Part2 => &[2],
+            Part::Both => &[1, 2],
         }
     }
 }
@@ -0,0 +1,121 @@
+use std::fmt::{self, Display};
+
+use crate::params::ParamError;
+
+/// Why a solution could not produce an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A line of the input could not be understood
+    Parse {
+        line: usize,
+        text: String,
+        reason: String,
+    },
+    /// The input was well formed, but it doesn't lead to an answer
+    NoSolution(String),
+    /// The day hasn't been solved yet
+    Unimplemented,
+    /// Something other than the input was wrong, like an unknown parameter
+    Argument(String),
+}
+
+/// Error returned by every solution
+///
+/// Solutions only know what went wrong and where in the input, so `day` and `part`
+/// are filled in by whoever called them (see `in_day` and `in_part`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+    pub kind: ErrorKind,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// `line` is 1-based, `text` is the offending input
+    pub fn parse(line: usize, text: &str, reason: impl Display) -> Self {
+        Self::from(ErrorKind::Parse {
+            line,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        })
+    }
+
+    pub fn no_solution(reason: impl Display) -> Self {
+        Self::from(ErrorKind::NoSolution(reason.to_string()))
+    }
+
+    /// A parameter or option doesn't work with this input, like one too large to count
+    pub fn argument(reason: impl Display) -> Self {
+        Self::from(ErrorKind::Argument(reason.to_string()))
+    }
+
+    pub fn unimplemented() -> Self {
+        Self::from(ErrorKind::Unimplemented)
+    }
+
+    /// Sets the day, unless it's already known
+    pub fn in_day(mut self, day: u8) -> Self {
+        self.day.get_or_insert(day);
+        self
+    }
+
+    /// Sets the part, unless it's already known
+    pub fn in_part(mut self, part: u8) -> Self {
+        self.part.get_or_insert(part);
+        self
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Self {
+            day: None,
+            part: None,
+            kind,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.day, self.part) {
+            (Some(day), Some(part)) => write!(f, "Day {} part {}: ", day, part)?,
+            (Some(day), None) => write!(f, "Day {}: ", day)?,
+            (None, Some(part)) => write!(f, "Part {}: ", part)?,
+            (None, None) => {}
+        }
+        match &self.kind {
+            ErrorKind::Parse { line, text, reason } if text.is_empty() => {
+                write!(f, "line {}: {}", line, reason)
+            }
+            ErrorKind::Parse { line, text, reason } => {
+                write!(f, "line {}: {} ({:?})", line, reason, text)
+            }
+            ErrorKind::NoSolution(reason) => write!(f, "no solution: {}", reason),
+            ErrorKind::Unimplemented => write!(f, "not implemented yet"),
+            ErrorKind::Argument(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParamError> for Error {
+    fn from(e: ParamError) -> Self {
+        Self::argument(e)
+    }
+}
+
+/// Attaches the position in the input to a failed conversion, e.g.
+/// `line.parse::<u64>().at_line(n, line)?`
+pub trait LineContext<T> {
+    fn at_line(self, line: usize, text: &str) -> Result<T>;
+}
+
+impl<T, E: Display> LineContext<T> for std::result::Result<T, E> {
+    fn at_line(self, line: usize, text: &str) -> Result<T> {
+        self.map_err(|e| Error::parse(line, text, e))
+    }
+}
@@ -0,0 +1,109 @@
+use color_eyre::eyre::{eyre, Result};
+use structopt::clap::AppSettings;
+use structopt::StructOpt;
+
+use aoc2021::day::{Day, Part, Year};
+use aoc2021::fixture::{self, Fixture};
+use aoc2021::registry;
+
+use crate::new::solution_path;
+use crate::verify::{table, Status};
+use crate::watch::{self, Watcher};
+
+#[derive(StructOpt)]
+#[structopt(setting = AppSettings::AllowMissingPositional)]
+pub struct Examples {
+    /// Event year, defaults to 2021
+    year: Option<Year>,
+    /// Problem day to run the examples of
+    day: Day,
+    /// Part to run
+    #[structopt(long, short, default_value)]
+    part: Part,
+    /// Which implementation of the day to run
+    #[structopt(long, default_value = registry::DEFAULT_VARIANT)]
+    variant: String,
+    /// Run again whenever the examples change, and rebuild when the solution changes
+    #[structopt(long, short)]
+    pub watch: bool,
+}
+
+impl Examples {
+    /// Returns the table, and how many examples failed or errored
+    pub fn run(&self) -> Result<(String, usize)> {
+        let day = self.day.in_year(self.year);
+        let code = day.get_variant(&self.variant).map_err(|e| eyre!(e))?;
+        let fixture = Fixture::load(day.year().get(), day.get())?.ok_or_else(|| {
+            eyre!(
+                "{} has no examples yet",
+                fixture::path(day.year().get(), day.get()).display()
+            )
+        })?;
+
+        let mut rows = vec![[
+            "Example".to_string(),
+            "Part".to_string(),
+            "Expected".to_string(),
+            "Actual".to_string(),
+            "Status".to_string(),
+        ]];
+        let mut failures = 0;
+        for (i, example) in fixture.examples.iter().enumerate() {
+            let name = example.name.clone().unwrap_or_else(|| (i + 1).to_string());
+            for &part in self.part.numbers() {
+                let expected = example.expected(part);
+                let params = example.params(code.params())?;
+                let actual = code
+                    .solve_input(example.input(), part, &params)
+                    .map(|answer| answer.to_submission())
+                    .map_err(|e| e.in_day(day.get()));
+                let status = match (&actual, expected) {
+                    (Err(_), _) => Status::Error,
+                    (Ok(_), None) => Status::Unknown,
+                    (Ok(actual), Some(expected)) if actual == expected => Status::Pass,
+                    (Ok(_), Some(_)) => Status::Fail,
+                };
+                if matches!(status, Status::Fail | Status::Error) {
+                    failures += 1;
+                }
+                rows.push([
+                    name.clone(),
+                    part.to_string(),
+                    expected.unwrap_or("-").to_string(),
+                    match actual {
+                        Ok(actual) => actual,
+                        Err(e) => e.to_string(),
+                    },
+                    status.as_str().to_string(),
+                ]);
+            }
+        }
+        Ok((table(&rows), failures))
+    }
+
+    /// `example --watch`. Only returns if something goes wrong
+    pub fn watch(&self) -> Result<()> {
+        let day = self.day.in_year(self.year);
+        let fixture = fixture::path(day.year().get(), day.get());
+        let source = solution_path(day);
+        let mut watcher = Watcher::new([fixture.clone(), source.clone()]);
+        loop {
+            match self.run() {
+                Ok((table, 0)) => println!("{}\nAll examples pass", table),
+                Ok((table, failures)) => println!("{}\n{} example(s) failed", table, failures),
+                Err(e) => println!("Error: {:#}", e),
+            }
+
+            let changed = watcher.wait();
+            println!();
+            if changed.contains(&source) {
+                println!("{} changed, rebuilding", source.display());
+                if let Err(e) = watch::rebuild_and_restart() {
+                    println!("Error: {:#}", e);
+                }
+            } else {
+                println!("{} changed", fixture.display());
+            }
+        }
+    }
+}
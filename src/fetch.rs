@@ -0,0 +1,70 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{bail, Result, WrapErr};
+use structopt::clap::AppSettings;
+use structopt::StructOpt;
+
+use aoc2021::day::{Day, Year};
+
+use crate::client::Client;
+use crate::run::input_path;
+
+#[derive(StructOpt)]
+#[structopt(setting = AppSettings::AllowMissingPositional)]
+pub struct Fetch {
+    /// Event year, defaults to 2021
+    year: Option<Year>,
+    /// Problem day to download the input for
+    day: Day,
+    /// Download the input again, even if it's already cached
+    #[structopt(long, short)]
+    force: bool,
+}
+
+/// Downloads the input for `day` into `path`
+fn download(day: Day, path: &Path) -> Result<()> {
+    let input = Client::from_env()?.input(day)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).wrap_err_with(|| format!("Could not create {}", dir.display()))?;
+    }
+    fs::write(path, input).wrap_err_with(|| format!("Could not write {}", path.display()))
+}
+
+/// An empty file is the placeholder left by `new`, and as good as missing
+pub fn is_cached(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0)
+}
+
+/// Path to the input for `day`, downloading it first if it isn't cached yet
+pub fn cached_input(day: Day) -> Result<PathBuf> {
+    let path = input_path(day)?;
+    if !is_cached(&path) {
+        if env::var("AOC_SESSION").is_err() {
+            bail!(
+                "{} is missing or empty, set AOC_SESSION to download it",
+                path.display()
+            );
+        }
+        download(day, &path).wrap_err_with(|| {
+            format!(
+                "{} is missing or empty, and downloading it failed",
+                path.display()
+            )
+        })?;
+    }
+    Ok(path)
+}
+
+impl Fetch {
+    pub fn fetch(&self) -> Result<String> {
+        let day = self.day.in_year(self.year);
+        let path = input_path(day)?;
+        if is_cached(&path) && !self.force {
+            return Ok(format!("{} is already cached", path.display()));
+        }
+        download(day, &path)?;
+        Ok(format!("Saved {}", path.display()))
+    }
+}
@@ -0,0 +1,104 @@
+//! Worked examples from the puzzle descriptions, kept in `fixtures/<year>/dayN.toml`:
+//!
+//! ```toml
+//! [[example]]
+//! name = "optional description"
+//! input = '''
+//! 1,2,3
+//! '''
+//! params = ["days=18"]
+//! part1 = "6"
+//! part2 = "36"
+//! ```
+//!
+//! Either part can be left out when the puzzle doesn't give its answer for that example
+
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::input;
+use crate::params::{Param, ParamError, Params};
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Fixture {
+    #[serde(default, rename = "example")]
+    pub examples: Vec<Example>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Example {
+    #[serde(default)]
+    pub name: Option<String>,
+    input: String,
+    /// Parameters other than the defaults, as given to `--extra`
+    #[serde(default)]
+    params: Vec<String>,
+    #[serde(default)]
+    pub part1: Option<String>,
+    #[serde(default)]
+    pub part2: Option<String>,
+}
+
+impl Example {
+    /// Normalized, same as the real inputs
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn params(&self, schema: &'static [Param]) -> Result<Params, ParamError> {
+        Params::parse(schema, &self.params)
+    }
+
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            _ => self.part2.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, toml::de::Error),
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(path, e) => write!(f, "Could not read {}: {}", path.display(), e),
+            LoadError::Parse(path, e) => write!(f, "Could not parse {}: {}", path.display(), e),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// `fixtures/<year>/dayN.toml` in the crate
+pub fn path(year: u16, day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(year.to_string())
+        .join(format!("day{}.toml", day))
+}
+
+impl Fixture {
+    /// `None` if the day has no fixture
+    pub fn load(year: u16, day: u8) -> Result<Option<Self>, LoadError> {
+        let path = path(year, day);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path).map_err(|e| LoadError::Io(path.clone(), e))?;
+        let mut fixture: Fixture =
+            toml::from_str(&contents).map_err(|e| LoadError::Parse(path, e))?;
+        for example in &mut fixture.examples {
+            example.input = input::normalize(&example.input);
+        }
+        Ok(Some(fixture))
+    }
+}
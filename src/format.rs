@@ -0,0 +1,240 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use time::Duration;
+
+use aoc2021::answer::Answer;
+use aoc2021::day::Day;
+use aoc2021::error::{Error, ErrorKind};
+
+use crate::supervise::{format_memory, TimedOut};
+
+/// How `run` and `run-all` print their results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Free-form, for people
+    #[default]
+    Text,
+    Json,
+    Csv,
+    /// A results table, to paste into a README
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => Err("Format needs to be one of text, json, csv or markdown"),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json"),
+            Format::Csv => write!(f, "csv"),
+            Format::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Error,
+    Unimplemented,
+    /// Killed by `--timeout`
+    Timeout,
+}
+
+impl Status {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Error => "error",
+            Status::Unimplemented => "unimplemented",
+            Status::Timeout => "timeout",
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// How one part of one day went
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<Answer>,
+    pub status: Status,
+    pub error: Option<String>,
+    /// Time spent parsing the input, in microseconds
+    pub parse_us: f64,
+    /// Time spent solving once the input was parsed, in microseconds
+    pub time_us: f64,
+    /// Peak memory of the process that solved it in KiB, with `--memory`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_memory_kb: Option<u64>,
+}
+
+impl Record {
+    pub fn new(
+        day: Day,
+        part: u8,
+        result: &aoc2021::error::Result<Answer>,
+        parse_time: Duration,
+        time: Duration,
+    ) -> Self {
+        let (answer, status, error) = match result {
+            Ok(answer) => (Some(answer.clone()), Status::Ok, None),
+            Err(Error {
+                kind: ErrorKind::Unimplemented,
+                ..
+            }) => (None, Status::Unimplemented, None),
+            Err(e) => (None, Status::Error, Some(e.to_string())),
+        };
+        Record {
+            year: day.year().get(),
+            day: day.get(),
+            part,
+            answer,
+            status,
+            error,
+            parse_us: parse_time.whole_nanoseconds() as f64 / 1_000.0,
+            time_us: time.whole_nanoseconds() as f64 / 1_000.0,
+            peak_memory_kb: None,
+        }
+    }
+
+    /// For days that couldn't be run at all, e.g. because the input is missing
+    pub fn failed(day: Day, part: u8, error: String) -> Self {
+        Record {
+            year: day.year().get(),
+            day: day.get(),
+            part,
+            answer: None,
+            status: Status::Error,
+            error: Some(error),
+            parse_us: 0.0,
+            time_us: 0.0,
+            peak_memory_kb: None,
+        }
+    }
+
+    /// For days that were killed by `--timeout`
+    pub fn timed_out(day: Day, part: u8, timeout: &TimedOut) -> Self {
+        Record {
+            status: Status::Timeout,
+            ..Record::failed(day, part, timeout.to_string())
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.status != Status::Ok
+    }
+}
+
+impl Format {
+    /// Renders `records` in any format but `Text`, which `run` and `run-all` lay out themselves
+    pub fn render(&self, records: &[Record]) -> serde_json::Result<String> {
+        Ok(match self {
+            Format::Text | Format::Json => serde_json::to_string_pretty(records)?,
+            Format::Csv => csv(records),
+            Format::Markdown => markdown(records),
+        })
+    }
+}
+
+fn csv(records: &[Record]) -> String {
+    let field = |field: &str| {
+        if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    };
+    let mut lines =
+        vec!["year,day,part,answer,status,error,parse_us,time_us,peak_memory_kb".to_string()];
+    for record in records {
+        lines.push(
+            [
+                record.year.to_string(),
+                record.day.to_string(),
+                record.part.to_string(),
+                field(
+                    &record
+                        .answer
+                        .as_ref()
+                        .map(Answer::to_submission)
+                        .unwrap_or_default(),
+                ),
+                record.status.as_str().to_string(),
+                field(record.error.as_deref().unwrap_or_default()),
+                format!("{:.3}", record.parse_us),
+                format!("{:.3}", record.time_us),
+                record
+                    .peak_memory_kb
+                    .map(|kb| kb.to_string())
+                    .unwrap_or_default(),
+            ]
+            .join(","),
+        );
+    }
+    lines.join("\n")
+}
+
+fn markdown(records: &[Record]) -> String {
+    let cell = |cell: &str| cell.replace('|', "\\|").replace('\n', " ");
+    // The memory column is only there when it was measured
+    let memory = records.iter().any(|record| record.peak_memory_kb.is_some());
+    let mut lines = vec![
+        "| Year | Day | Part | Answer | Status | Parse | Time |".to_string(),
+        "| ---: | ---: | ---: | --- | --- | ---: | ---: |".to_string(),
+    ];
+    if memory {
+        lines[0].push_str(" Memory |");
+        lines[1].push_str(" ---: |");
+    }
+    for record in records {
+        let answer = match (&record.answer, &record.error) {
+            (Some(answer), _) => cell(&answer.to_submission()),
+            (None, Some(error)) => cell(error),
+            (None, None) => "-".to_string(),
+        };
+        let time = |micros: f64| match record.status {
+            Status::Ok => format!("{:.0}μs", micros),
+            _ => "-".to_string(),
+        };
+        let mut line = format!(
+            "| {} | {} | {} | {} | {} | {} | {} |",
+            record.year,
+            record.day,
+            record.part,
+            answer,
+            record.status.as_str(),
+            time(record.parse_us),
+            time(record.time_us)
+        );
+        if memory {
+            let kb = record.peak_memory_kb.map_or("-".to_string(), format_memory);
+            line.push_str(&format!(" {} |", kb));
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,12 @@
+//! Inputs come from files, stdin, the command line and fixtures, any of which can add a BOM,
+//! Windows line endings or trailing newlines
+
+/// Strips a leading BOM, turns CRLF into LF and trims trailing newlines, so solutions can
+/// split on whatever separator the puzzle uses without tripping over the last line
+pub fn normalize(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    input
+        .replace("\r\n", "\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
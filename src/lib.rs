@@ -1,51 +1,212 @@
+//! Advent of Code solutions. Other programs can solve puzzles through `solve` and
+//! `solve_both`, and find out what there is to solve with `days` and `params`:
+//!
+//! ```
+//! use aoc2021::day::{Day, Year};
+//!
+//! let day = Day::new(Year::DEFAULT, 6).unwrap();
+//! let answer = aoc2021::solve(day, 1, "3,4,3,1,2", &["days=18".to_string()]).unwrap();
+//! assert_eq!(answer.to_submission(), "26");
+//! ```
+
+use std::any::Any;
+
+use answer::Answer;
+use day::{Day, Year};
+use error::{Error, Result};
+use params::{Param, Params};
+
 /// Trait for every solution
 ///
-/// All methods take a `dyn Read`. It can be given a file object (actual input) or a string (test cases)
+/// `parse` takes the input as a string, which can be an actual input or a test case. Runners
+/// pass it through `input::normalize` first. Both parts then share what it parsed, so the
+/// input is only parsed once however many parts are solved
 ///
-/// Extra args are for cases where there are extra details in the question that are not part of the input itself
-pub trait AoCDay {
-    fn part1(&self, input: &str, extra_args: &[String]) -> String;
-    fn part2(&self, input: &str, extra_args: &[String]) -> String;
+/// Params are for details of the question that are not part of the input itself, like how many
+/// steps to simulate. Each day declares the ones it takes in `params`
+///
+/// Malformed input is reported through `error::Error` rather than panicking, so one bad day
+/// doesn't take the others down with it
+pub trait Solution: Sync {
+    /// The input, parsed into whatever both parts need
+    type Parsed: 'static;
+
+    fn parse(&self, input: &str, params: &Params) -> Result<Self::Parsed>;
+    fn part1(&self, parsed: &Self::Parsed, params: &Params) -> Result<Answer>;
+    fn part2(&self, parsed: &Self::Parsed, params: &Params) -> Result<Answer>;
     /// This method should be implemented if solving both parts together is more efficient than doing them one at a time
-    fn both(&self, input: &str, extra_args: &[String]) -> String {
-        let p1 = self.part1(input, extra_args);
-        let p2 = self.part2(input, extra_args);
-        format!(
-            "Part1: {}\n\
-            Part2: {}",
-            p1, p2
-        )
+    fn both(&self, parsed: &Self::Parsed, params: &Params) -> Result<(Answer, Answer)> {
+        let span = profile::span("part1");
+        let p1 = self.part1(parsed, params).map_err(|e| e.in_part(1))?;
+        drop(span);
+        let _span = profile::span("part2");
+        let p2 = self.part2(parsed, params).map_err(|e| e.in_part(2))?;
+        Ok((p1, p2))
+    }
+    /// The parameters `parse`, `part1` and `part2` look up in `params`
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+    /// Stubs for days that haven't been solved yet return false, so runners can skip them
+    fn implemented(&self) -> bool {
+        true
+    }
+}
+
+/// A `Solution::Parsed` of any day
+pub type Parsed = Box<dyn Any>;
+
+/// `Solution` without the associated type, so that days can be told apart at runtime only (see
+/// `registry`). Every `Solution` is one
+pub trait AoCDay: Sync {
+    fn parse(&self, input: &str, params: &Params) -> Result<Parsed>;
+    /// Solves part 1 or 2 of what `parse` returned
+    fn solve(&self, parsed: &Parsed, part: u8, params: &Params) -> Result<Answer>;
+    fn solve_both(&self, parsed: &Parsed, params: &Params) -> Result<(Answer, Answer)>;
+    fn params(&self) -> &'static [Param];
+    fn implemented(&self) -> bool;
+
+    /// Parses and solves part 1
+    fn part1(&self, input: &str, params: &Params) -> Result<Answer> {
+        self.solve(&self.parse(input, params)?, 1, params)
+    }
+
+    /// Parses and solves part 2
+    fn part2(&self, input: &str, params: &Params) -> Result<Answer> {
+        self.solve(&self.parse(input, params)?, 2, params)
     }
+
+    /// Parses and solves part 1 or 2, with the error saying which part failed
+    fn solve_input(&self, input: &str, part: u8, params: &Params) -> Result<Answer> {
+        self.solve(&self.parse(input, params)?, part, params)
+            .map_err(|e| e.in_part(part))
+    }
+
+    /// Parses once and solves both parts
+    fn both(&self, input: &str, params: &Params) -> Result<(Answer, Answer)> {
+        self.solve_both(&self.parse(input, params)?, params)
+    }
+}
+
+/// The parsed input handed back to `solve` always comes from the same day's `parse`, anything
+/// else is a bug in the runner
+fn downcast<T: Solution>(parsed: &Parsed) -> &T::Parsed {
+    parsed
+        .downcast_ref()
+        .expect("parsed input of another solution")
 }
 
+impl<T: Solution> AoCDay for T {
+    fn parse(&self, input: &str, params: &Params) -> Result<Parsed> {
+        let _span = profile::span("parse");
+        Ok(Box::new(Solution::parse(self, input, params)?))
+    }
+
+    fn solve(&self, parsed: &Parsed, part: u8, params: &Params) -> Result<Answer> {
+        match part {
+            1 => {
+                let _span = profile::span("part1");
+                Solution::part1(self, downcast::<T>(parsed), params)
+            }
+            _ => {
+                let _span = profile::span("part2");
+                Solution::part2(self, downcast::<T>(parsed), params)
+            }
+        }
+    }
+
+    fn solve_both(&self, parsed: &Parsed, params: &Params) -> Result<(Answer, Answer)> {
+        let _span = profile::span("both");
+        Solution::both(self, downcast::<T>(parsed), params)
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Solution::params(self)
+    }
+
+    fn implemented(&self) -> bool {
+        Solution::implemented(self)
+    }
+}
+
+/// Every day that has a solution, in order
+pub fn days() -> Vec<Day> {
+    registry::all()
+        .into_iter()
+        .filter(|r| r.variant == registry::DEFAULT_VARIANT && r.code.implemented())
+        .filter_map(|r| Day::new(Year::new(r.year).ok()?, r.day).ok())
+        .collect()
+}
+
+/// The parameters `solve` takes for `day`, with their defaults
+pub fn params(day: Day) -> &'static [Param] {
+    day.get_code().params()
+}
+
+/// Solves part 1 or 2 of `day`. The input is normalized first, and `params` are given as
+/// `name=value`, the same as `--extra`
+pub fn solve(day: Day, part: u8, input: &str, params: &[String]) -> Result<Answer> {
+    if !(1..=2).contains(&part) {
+        let reason = format!("there is no part {}", part);
+        return Err(Error::argument(reason).in_day(day.get()));
+    }
+    let code = day.get_code();
+    let params =
+        Params::parse(code.params(), params).map_err(|e| Error::from(e).in_day(day.get()))?;
+    let parsed = code
+        .parse(&input::normalize(input), &params)
+        .map_err(|e| e.in_day(day.get()))?;
+    code.solve(&parsed, part, &params)
+        .map_err(|e| e.in_day(day.get()).in_part(part))
+}
+
+/// Solves both parts of `day`, parsing the input only once
+pub fn solve_both(day: Day, input: &str, params: &[String]) -> Result<(Answer, Answer)> {
+    let code = day.get_code();
+    let params =
+        Params::parse(code.params(), params).map_err(|e| Error::from(e).in_day(day.get()))?;
+    code.both(&input::normalize(input), &params)
+        .map_err(|e| e.in_day(day.get()))
+}
+
+pub mod answer;
 pub mod computer;
+pub mod day;
+pub mod error;
+pub mod fixture;
+pub mod input;
+pub mod params;
+pub mod profile;
+pub mod registry;
 
 pub mod solutions {
-    pub mod day1;
-    pub mod day10;
-    pub mod day11;
-    pub mod day12;
-    pub mod day13;
-    pub mod day14;
-    pub mod day15;
-    pub mod day16;
-    pub mod day17;
-    pub mod day18;
-    pub mod day19;
-    pub mod day2;
-    pub mod day20;
-    pub mod day21;
-    pub mod day22;
-    pub mod day23;
-    pub mod day24;
-    pub mod day25;
-    pub mod day3;
-    pub mod day4;
-    pub mod day5;
-    pub mod day6;
-    pub mod day7;
-    pub mod day8;
-    pub mod day9;
-}
-
-pub use solutions::*;
+    pub mod year2021 {
+        pub mod day1;
+        pub mod day10;
+        pub mod day11;
+        pub mod day12;
+        pub mod day13;
+        pub mod day14;
+        pub mod day15;
+        pub mod day16;
+        pub mod day17;
+        pub mod day18;
+        pub mod day19;
+        pub mod day2;
+        pub mod day20;
+        pub mod day21;
+        pub mod day22;
+        pub mod day23;
+        pub mod day24;
+        pub mod day25;
+        pub mod day3;
+        pub mod day4;
+        pub mod day5;
+        pub mod day6;
+        pub mod day7;
+        pub mod day8;
+        pub mod day9;
+    }
+}
+
+pub use solutions::year2021::*;
@@ -1,11 +1,29 @@
-use color_eyre::eyre::Result;
+use std::env;
+
+use color_eyre::eyre::{bail, Result};
 use dotenv::dotenv;
+use structopt::clap::{AppSettings, ErrorKind};
 use structopt::StructOpt;
 
-mod day;
+mod bench;
+mod client;
+mod example;
+mod fetch;
+mod format;
+mod new;
 mod run;
+mod submit;
+mod supervise;
+mod verify;
+mod watch;
 
+use example::Examples;
+use fetch::Fetch;
+use new::New;
 use run::{Run, RunAll};
+use submit::Submit;
+use supervise::Worker;
+use verify::{Record, Verify};
 
 #[derive(StructOpt)]
 #[structopt(name = "Advent Of Code")]
@@ -14,30 +32,96 @@ enum Args {
     Run(Run),
     /// Run code of all days
     RunAll(RunAll),
+    /// Run the worked examples of the given day
+    Example(Examples),
+    /// Check every input against its recorded answers
+    Verify(Verify),
+    /// Record a confirmed answer for an input
+    Record(Record),
+    /// Download the input of the given day
+    Fetch(Fetch),
+    /// Submit the answer of the given day
+    Submit(Submit),
+    /// Start the given day: a solution from the template, an example fixture and an empty input
+    New(New),
+    #[structopt(setting = AppSettings::Hidden)]
+    Worker(Worker),
+}
+
+/// The parameters of the day, for `run <day> --help`. The help is static, so this parses the
+/// arguments again without the --help to find out which day it's about
+fn params_help() -> Option<String> {
+    let args = env::args_os().filter(|arg| arg != "--help" && arg != "-h");
+    match Args::from_iter_safe(args) {
+        Ok(Args::Run(run)) => run.params_help(),
+        _ => None,
+    }
 }
 
 fn main() -> Result<()> {
     color_eyre::install()?;
     dotenv()?;
-    let args = Args::from_args();
+    let args = match Args::from_iter_safe(env::args_os()) {
+        Ok(args) => args,
+        Err(e) if e.kind == ErrorKind::HelpDisplayed => {
+            println!("{}", e.message);
+            if let Some(help) = params_help() {
+                println!("\n{}", help);
+            }
+            return Ok(());
+        }
+        Err(e) => e.exit(),
+    };
     match args {
         Args::RunAll(run_all) => {
-            let (output, duration) = run_all.run_all()?;
+            let (output, failures) = run_all.run_all()?;
             println!("{}", output);
-            if let Some(duration) = duration {
-                println!(
-                    "Time: {}s",
-                    (duration.subsec_microseconds() as f64) / 1000000.0
-                );
+            if failures > 0 {
+                bail!("{} day(s) failed", failures);
             }
         }
+        Args::Run(run) if run.watch => {
+            run.watch()?;
+        }
         Args::Run(run) => {
-            let (output, duration) = run.run()?;
+            let (output, failures) = run.run()?;
             println!("{}", output);
-            if let Some(duration) = duration {
-                println!("Time: {}μs", duration.whole_microseconds());
+            if failures > 0 {
+                bail!("{} part(s) failed", failures);
             }
         }
+        Args::Example(examples) if examples.watch => {
+            examples.watch()?;
+        }
+        Args::Example(examples) => {
+            let (table, failures) = examples.run()?;
+            println!("{}", table);
+            if failures > 0 {
+                bail!("{} example(s) failed", failures);
+            }
+        }
+        Args::Verify(verify) => {
+            let (table, failures) = verify.verify()?;
+            println!("{}", table);
+            if failures > 0 {
+                bail!("{} answer(s) did not verify", failures);
+            }
+        }
+        Args::Record(record) => {
+            println!("{}", record.record()?);
+        }
+        Args::Fetch(fetch) => {
+            println!("{}", fetch.fetch()?);
+        }
+        Args::Submit(submit) => {
+            println!("{}", submit.submit()?);
+        }
+        Args::New(new) => {
+            println!("{}", new.new_day()?);
+        }
+        Args::Worker(worker) => {
+            println!("{}", worker.work()?);
+        }
     }
     Ok(())
 }
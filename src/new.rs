@@ -0,0 +1,135 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use structopt::clap::AppSettings;
+use structopt::StructOpt;
+
+use aoc2021::day::{Day, Year};
+use aoc2021::fixture;
+
+use crate::run::input_path;
+
+const SOLUTION_TEMPLATE: &str = include_str!("../templates/solution.rs");
+const FIXTURE_TEMPLATE: &str = include_str!("../templates/fixture.toml");
+
+#[derive(StructOpt)]
+#[structopt(setting = AppSettings::AllowMissingPositional)]
+pub struct New {
+    /// Event year, defaults to 2021
+    year: Option<Year>,
+    /// Problem day to start
+    day: Day,
+    /// Replace the solution even if it isn't the untouched template
+    #[structopt(long)]
+    force: bool,
+}
+
+/// Where the solution of a day lives, in the crate
+pub fn solution_path(day: Day) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src/solutions")
+        .join(format!("year{}", day.year()))
+        .join(format!("day{}.rs", day.get()))
+}
+
+/// Adds `pub mod dayN;` to the year's module in `lib.rs`, adding the year too if needed.
+/// Returns false if it was already there
+fn declare_module(day: Day) -> Result<bool> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs");
+    let lib =
+        fs::read_to_string(&path).wrap_err_with(|| format!("Could not read {}", path.display()))?;
+    let year_module = format!("    pub mod year{} {{\n", day.year());
+    let day_module = format!("        pub mod day{};\n", day.get());
+
+    let lib = match lib.find(&year_module) {
+        Some(start) => {
+            let body_start = start + year_module.len();
+            let body_end = body_start
+                + lib[body_start..]
+                    .find("    }\n")
+                    .ok_or_else(|| eyre!("Could not find the end of year{}", day.year()))?;
+            let mut modules = lib[body_start..body_end]
+                .lines()
+                .map(|line| format!("{}\n", line))
+                .collect::<Vec<_>>();
+            if modules.contains(&day_module) {
+                return Ok(false);
+            }
+            modules.push(day_module);
+            modules.sort();
+            format!(
+                "{}{}{}",
+                &lib[..body_start],
+                modules.concat(),
+                &lib[body_end..]
+            )
+        }
+        None => {
+            let solutions = "pub mod solutions {\n";
+            let start = lib
+                .find(solutions)
+                .ok_or_else(|| eyre!("Could not find the solutions module"))?
+                + solutions.len();
+            format!(
+                "{}{}{}    }}\n{}",
+                &lib[..start],
+                year_module,
+                day_module,
+                &lib[start..]
+            )
+        }
+    };
+    fs::write(&path, lib).wrap_err_with(|| format!("Could not write {}", path.display()))?;
+    Ok(true)
+}
+
+/// Writes `contents` to `path` unless there's something there already
+fn create(path: &Path, contents: &str, output: &mut Vec<String>) -> Result<()> {
+    if path.exists() {
+        return Ok(());
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).wrap_err_with(|| format!("Could not create {}", dir.display()))?;
+    }
+    fs::write(path, contents).wrap_err_with(|| format!("Could not write {}", path.display()))?;
+    output.push(format!("Created {}", path.display()));
+    Ok(())
+}
+
+impl New {
+    pub fn new_day(&self) -> Result<String> {
+        let day = self.day.in_year(self.year);
+        let solution = solution_path(day);
+        let code = SOLUTION_TEMPLATE
+            .replace("{year}", &day.year().to_string())
+            .replace("{day}", &day.get().to_string());
+        // The untouched template can be written again, anything else might be someone's work
+        if let Ok(existing) = fs::read_to_string(&solution) {
+            if existing != code && !self.force {
+                bail!(
+                    "{} already exists and isn't the template, use --force to replace it",
+                    solution.display()
+                );
+            }
+        }
+
+        let mut output = vec![];
+        if let Some(dir) = solution.parent() {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("Could not create {}", dir.display()))?;
+        }
+        fs::write(&solution, code)
+            .wrap_err_with(|| format!("Could not write {}", solution.display()))?;
+        output.push(format!("Wrote {}", solution.display()));
+        if declare_module(day)? {
+            output.push(format!("Added day{} to src/lib.rs", day.get()));
+        }
+
+        let fixture_template = FIXTURE_TEMPLATE.replace("{day}", &day.get().to_string());
+        let fixture = fixture::path(day.year().get(), day.get());
+        create(&fixture, &fixture_template, &mut output)?;
+        create(&input_path(day)?, "", &mut output)?;
+        Ok(output.join("\n"))
+    }
+}
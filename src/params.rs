@@ -0,0 +1,204 @@
+//! Parameters of a day that the puzzle fixes but that variants of it change, e.g. how many
+//! days day 6 simulates. Each day declares them with their defaults:
+//!
+//! ```ignore
+//! const PARAMS: &[Param] = &[Param::int("days", "Days to simulate", 80).part2(256)];
+//! ```
+//!
+//! and they're set on the command line as `--extra days=18`
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+/// What values a parameter takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Int { min: i64, max: i64 },
+    Flag,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Value {
+    Int(i64),
+    Flag(bool),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{}", n),
+            Value::Flag(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+/// One parameter of a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub kind: Kind,
+    /// Defaults of part 1 and part 2, which are often the only difference between them
+    pub defaults: [Value; 2],
+}
+
+impl Param {
+    /// A non-negative integer
+    pub const fn int(name: &'static str, help: &'static str, default: i64) -> Self {
+        Self {
+            name,
+            help,
+            kind: Kind::Int {
+                min: 0,
+                max: i64::MAX,
+            },
+            defaults: [Value::Int(default), Value::Int(default)],
+        }
+    }
+
+    /// Off unless it's given, `name` alone turns it on
+    pub const fn flag(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            kind: Kind::Flag,
+            defaults: [Value::Flag(false), Value::Flag(false)],
+        }
+    }
+
+    /// Limits an integer to `min..=max`
+    pub const fn range(mut self, min: i64, max: i64) -> Self {
+        if let Kind::Int { .. } = self.kind {
+            self.kind = Kind::Int { min, max };
+        }
+        self
+    }
+
+    /// A different default for part 2
+    pub const fn part2(mut self, default: i64) -> Self {
+        self.defaults[1] = Value::Int(default);
+        self
+    }
+
+    fn parse(&self, value: Option<&str>) -> Result<Value, ParamError> {
+        let invalid = |reason: String| ParamError::Invalid(self.name.to_string(), reason);
+        match (self.kind, value) {
+            (Kind::Flag, None) => Ok(Value::Flag(true)),
+            (Kind::Flag, Some(value)) => value
+                .parse()
+                .map(Value::Flag)
+                .map_err(|_| invalid(format!("{:?} is not true or false", value))),
+            (Kind::Int { .. }, None) => Err(invalid("needs a value".to_string())),
+            (Kind::Int { min, max }, Some(value)) => match value.parse::<i64>() {
+                Ok(n) if (min..=max).contains(&n) => Ok(Value::Int(n)),
+                Ok(n) if max == i64::MAX => Err(invalid(format!("{} is less than {}", n, min))),
+                Ok(n) => Err(invalid(format!("{} is not in {}..={}", n, min, max))),
+                Err(_) => Err(invalid(format!("{:?} is not an integer", value))),
+            },
+        }
+    }
+
+    /// e.g. `days=<int>  Days to simulate (default: 80, 256 for part 2)`
+    pub fn usage(&self) -> (String, String) {
+        let syntax = match self.kind {
+            Kind::Int { .. } => format!("{}=<int>", self.name),
+            Kind::Flag => self.name.to_string(),
+        };
+        let default = match self.defaults {
+            [Value::Flag(_), _] => String::new(),
+            [part1, part2] if part1 == part2 => format!(" (default: {})", part1),
+            [part1, part2] => format!(" (default: {}, {} for part 2)", part1, part2),
+        };
+        let range = match self.kind {
+            Kind::Int { min, max } if max != i64::MAX => format!(" [{}..={}]", min, max),
+            _ => String::new(),
+        };
+        (syntax, format!("{}{}{}", self.help, range, default))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    Unknown(String),
+    Invalid(String, String),
+}
+
+impl Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamError::Unknown(name) => write!(f, "Unknown parameter {:?}", name),
+            ParamError::Invalid(name, reason) => write!(f, "Parameter {}: {}", name, reason),
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// The parameters a day is run with: whatever was given, and the defaults for the rest
+#[derive(Debug, Clone)]
+pub struct Params {
+    schema: &'static [Param],
+    values: BTreeMap<&'static str, Value>,
+}
+
+impl Params {
+    /// Every default
+    pub fn new(schema: &'static [Param]) -> Self {
+        Self {
+            schema,
+            values: BTreeMap::new(),
+        }
+    }
+
+    /// Checks `args`, each `name=value` (or `name` for flags), against `schema`
+    pub fn parse(schema: &'static [Param], args: &[String]) -> Result<Self, ParamError> {
+        let mut params = Self::new(schema);
+        for arg in args {
+            let (name, value) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (arg.as_str(), None),
+            };
+            let param = params
+                .find(name)
+                .ok_or_else(|| ParamError::Unknown(name.to_string()))?;
+            params.values.insert(param.name, param.parse(value)?);
+        }
+        Ok(params)
+    }
+
+    fn find(&self, name: &str) -> Option<&'static Param> {
+        self.schema.iter().find(|param| param.name == name)
+    }
+
+    /// The value of `name` in `part`. Days only ask for parameters they declare, so an
+    /// unknown one is a bug in the day
+    fn get(&self, name: &str, part: u8) -> Value {
+        let param = self
+            .find(name)
+            .unwrap_or_else(|| panic!("{:?} is not a declared parameter", name));
+        self.values
+            .get(name)
+            .copied()
+            .unwrap_or(param.defaults[usize::from(part == 2)])
+    }
+
+    /// An integer parameter with a single default
+    pub fn int(&self, name: &str) -> i64 {
+        self.int_in(name, 1)
+    }
+
+    /// An integer parameter whose default depends on the part
+    pub fn int_in(&self, name: &str, part: u8) -> i64 {
+        match self.get(name, part) {
+            Value::Int(n) => n,
+            Value::Flag(_) => panic!("{:?} is not an integer parameter", name),
+        }
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        match self.get(name, 1) {
+            Value::Flag(b) => b,
+            Value::Int(_) => panic!("{:?} is not a flag", name),
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Spans that mark the phases of a solution, to see where its time goes:
+//!
+//! ```
+//! use aoc2021::profile;
+//!
+//! profile::start();
+//! {
+//!     let _span = profile::span("fold");
+//!     // ...
+//! }
+//! let profile = profile::finish();
+//! assert_eq!(profile.summary()[0].name, "fold");
+//! ```
+//!
+//! A span lasts until it's dropped, and the ones opened inside it are its children. Nothing is
+//! recorded unless profiling was started, and then spans only cost a clock read and a lock
+
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SPANS: Mutex<Vec<Closed>> = Mutex::new(vec![]);
+static ORIGIN: Mutex<Option<Instant>> = Mutex::new(None);
+static NEXT_THREAD: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Names of the spans open on this thread, outermost first
+    static OPEN: RefCell<Vec<&'static str>> = const { RefCell::new(vec![]) };
+    /// Small numbers are easier to follow in a trace than the system's thread ids
+    static THREAD: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+fn thread() -> usize {
+    THREAD.with(|thread| match thread.get() {
+        Some(id) => id,
+        None => {
+            let id = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+            thread.set(Some(id));
+            id
+        }
+    })
+}
+
+/// A span that ended
+#[derive(Debug, Clone)]
+struct Closed {
+    /// Names from the outermost span down to this one
+    path: Vec<&'static str>,
+    thread: usize,
+    start: Instant,
+    duration: Duration,
+}
+
+/// Records how long it lived when dropped, if profiling is on
+#[must_use = "a span ends as soon as it's dropped"]
+pub struct Span {
+    name: &'static str,
+    start: Option<Instant>,
+}
+
+/// Opens a span, which lasts until the returned value is dropped
+pub fn span(name: &'static str) -> Span {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return Span { name, start: None };
+    }
+    OPEN.with(|open| open.borrow_mut().push(name));
+    Span {
+        name,
+        start: Some(Instant::now()),
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let start = match self.start {
+            Some(start) => start,
+            None => return,
+        };
+        let duration = start.elapsed();
+        let path = OPEN.with(|open| {
+            let mut open = open.borrow_mut();
+            let path = open.clone();
+            open.pop();
+            path
+        });
+        debug_assert_eq!(path.last(), Some(&self.name));
+        SPANS.lock().unwrap().push(Closed {
+            path,
+            thread: thread(),
+            start,
+            duration,
+        });
+    }
+}
+
+/// Starts recording spans, forgetting any from before
+pub fn start() {
+    SPANS.lock().unwrap().clear();
+    *ORIGIN.lock().unwrap() = Some(Instant::now());
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Stops recording spans, and returns the ones recorded since `start`
+pub fn finish() -> Profile {
+    ENABLED.store(false, Ordering::Relaxed);
+    let mut spans = std::mem::take(&mut *SPANS.lock().unwrap());
+    spans.sort_by_key(|span| (span.start, span.path.len()));
+    let origin = ORIGIN.lock().unwrap().take();
+    Profile {
+        origin: origin.or_else(|| spans.first().map(|span| span.start)),
+        spans,
+    }
+}
+
+/// Every span recorded between `start` and `finish`
+#[derive(Debug)]
+pub struct Profile {
+    origin: Option<Instant>,
+    spans: Vec<Closed>,
+}
+
+/// Every span with the same name under the same parents, added up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub name: &'static str,
+    /// How many spans it's nested in
+    pub depth: usize,
+    pub total: Duration,
+    pub calls: usize,
+}
+
+/// One complete event of the Chrome trace event format, in microseconds
+#[derive(Serialize)]
+struct TraceEvent {
+    name: &'static str,
+    ph: &'static str,
+    ts: f64,
+    dur: f64,
+    pid: u32,
+    tid: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Trace {
+    trace_events: Vec<TraceEvent>,
+}
+
+impl Profile {
+    /// The spans as a tree, parents before their children and each in the order it first
+    /// started
+    pub fn summary(&self) -> Vec<Summary> {
+        let mut paths: Vec<(&[&'static str], Summary)> = vec![];
+        for span in &self.spans {
+            match paths
+                .iter_mut()
+                .find(|(path, _)| *path == span.path.as_slice())
+            {
+                Some((_, summary)) => {
+                    summary.total += span.duration;
+                    summary.calls += 1;
+                }
+                None => paths.push((
+                    &span.path,
+                    Summary {
+                        name: span.path.last().copied().unwrap_or_default(),
+                        depth: span.path.len() - 1,
+                        total: span.duration,
+                        calls: 1,
+                    },
+                )),
+            }
+        }
+        // Depth first, so that children follow their parent rather than whatever started
+        // after it
+        let mut ordered = Vec::with_capacity(paths.len());
+        let mut stack = paths
+            .iter()
+            .filter(|(path, _)| path.len() == 1)
+            .rev()
+            .collect::<Vec<_>>();
+        while let Some(entry @ (path, _)) = stack.pop() {
+            ordered.push(entry.1.clone());
+            let children = paths
+                .iter()
+                .filter(|(child, _)| child.len() == path.len() + 1 && child.starts_with(path));
+            stack.extend(children.rev());
+        }
+        ordered
+    }
+
+    /// The spans in the Chrome trace event format, for chrome://tracing or Perfetto
+    pub fn chrome_trace(&self) -> serde_json::Result<String> {
+        let micros = |duration: Duration| duration.as_nanos() as f64 / 1_000.0;
+        // Without an origin there are no spans either
+        let origin = self.origin.unwrap_or_else(Instant::now);
+        let trace_events = self
+            .spans
+            .iter()
+            .map(|span| TraceEvent {
+                name: span.path.last().copied().unwrap_or_default(),
+                ph: "X",
+                ts: micros(span.start.saturating_duration_since(origin)),
+                dur: micros(span.duration),
+                pid: std::process::id(),
+                tid: span.thread,
+            })
+            .collect();
+        serde_json::to_string(&Trace { trace_events })
+    }
+}
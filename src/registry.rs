@@ -0,0 +1,98 @@
+//! Every solution registers itself here with `register!`, so finding the code for a day
+//! doesn't need a hand-written list of them
+
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::params::Params;
+use crate::{AoCDay, Solution};
+
+/// Name of the main implementation of a day
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// One implementation of one day
+pub struct Registration {
+    pub year: u16,
+    pub day: u8,
+    /// Tells apart implementations of the same day, `DEFAULT_VARIANT` for the main one
+    pub variant: &'static str,
+    pub code: &'static dyn AoCDay,
+}
+
+inventory::collect!(Registration);
+
+/// Registers the `Code` of a solution module as the default variant of a day, or any
+/// other value as a named variant:
+///
+/// ```ignore
+/// register!(2021, 15);
+/// register!(2021, 15, "grid", Grid);
+/// ```
+#[macro_export]
+macro_rules! register {
+    ($year:expr, $day:expr) => {
+        $crate::register!($year, $day, $crate::registry::DEFAULT_VARIANT, Code);
+    };
+    ($year:expr, $day:expr, $variant:expr, $code:expr) => {
+        ::inventory::submit! {
+            $crate::registry::Registration {
+                year: $year,
+                day: $day,
+                variant: $variant,
+                code: &$code,
+            }
+        }
+    };
+}
+
+/// Stands in for days nothing is registered for
+struct Unregistered;
+
+impl Solution for Unregistered {
+    type Parsed = ();
+
+    fn parse(&self, _input: &str, _params: &Params) -> Result<()> {
+        Err(Error::unimplemented())
+    }
+
+    fn part1(&self, _parsed: &(), _params: &Params) -> Result<Answer> {
+        Err(Error::unimplemented())
+    }
+
+    fn part2(&self, _parsed: &(), _params: &Params) -> Result<Answer> {
+        Err(Error::unimplemented())
+    }
+
+    fn implemented(&self) -> bool {
+        false
+    }
+}
+
+/// Every registered solution, ordered by year, day and variant (the default one first)
+pub fn all() -> Vec<&'static Registration> {
+    let mut registrations = inventory::iter::<Registration>
+        .into_iter()
+        .collect::<Vec<_>>();
+    registrations.sort_by_key(|r| (r.year, r.day, r.variant != DEFAULT_VARIANT, r.variant));
+    registrations
+}
+
+pub fn get(year: u16, day: u8, variant: &str) -> Option<&'static dyn AoCDay> {
+    inventory::iter::<Registration>
+        .into_iter()
+        .find(|r| r.year == year && r.day == day && r.variant == variant)
+        .map(|r| r.code)
+}
+
+/// The default variant of a day, or a stub that reports it as unimplemented
+pub fn get_default(year: u16, day: u8) -> &'static dyn AoCDay {
+    get(year, day, DEFAULT_VARIANT).unwrap_or(&Unregistered)
+}
+
+/// Names of the variants of a day
+pub fn variants(year: u16, day: u8) -> Vec<&'static str> {
+    all()
+        .into_iter()
+        .filter(|r| r.year == year && r.day == day)
+        .map(|r| r.variant)
+        .collect()
+}
@@ -1,156 +1,617 @@
 use std::env;
-use std::fmt::Display;
-use std::fs::File;
-use std::io::Read;
+use std::fs::{self, File};
+use std::io::{self, Read};
 use std::num::NonZeroU8;
-use std::ops::Sub;
 use std::path::{Path, PathBuf};
-use std::str::FromStr;
 extern crate time;
 use time::Duration;
 use time::Instant;
 
-use color_eyre::eyre::Result;
+use std::collections::BTreeSet;
+
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use rayon::prelude::*;
+use structopt::clap::AppSettings;
 use structopt::StructOpt;
 
-use crate::day::Day;
+use aoc2021::answer::Answer;
+use aoc2021::day::{Day, Days, Part, Year};
+use aoc2021::input;
+use aoc2021::params::{Param, Params};
+use aoc2021::profile;
+use aoc2021::registry::{self, DEFAULT_VARIANT};
+use aoc2021::{AoCDay, Parsed};
+
+use crate::bench::{self, Bench, Benchmark};
+use crate::fetch::cached_input;
+use crate::format::{Format, Record};
+use crate::new::solution_path;
+use crate::supervise::{format_memory, Report, Supervision, TimedOut};
+use crate::watch::{self, Watcher};
 
 #[derive(StructOpt)]
+#[structopt(setting = AppSettings::AllowMissingPositional)]
 pub struct Run {
+    /// Event year, defaults to 2021
+    year: Option<Year>,
     /// Problem day to run
     day: Day,
-    /// Path to input file
-    #[structopt(long)]
+    /// Path to input file, or - to read it from stdin
+    #[structopt(long, conflicts_with = "input-str")]
     input: Option<PathBuf>,
-    /// Any extra arguments in question
+    /// The input itself, for small test cases
+    #[structopt(long)]
+    input_str: Option<String>,
+    /// Parameters of the day as name=value, listed in --help along with the day
     #[structopt(long)]
     extra: Vec<String>,
     /// Part to run
     #[structopt(long, short, default_value)]
     part: Part,
-    /// Benchmark
+    /// Which implementation of the day to run
+    #[structopt(long, default_value = registry::DEFAULT_VARIANT)]
+    variant: String,
+    #[structopt(flatten)]
+    bench: Bench,
+    #[structopt(flatten)]
+    supervision: Supervision,
+    /// Output format: text, json, csv or markdown
+    #[structopt(long, default_value)]
+    format: Format,
+    /// Run again whenever the input changes, and rebuild when the solution changes
     #[structopt(long, short)]
-    bench: bool,
+    pub watch: bool,
+    /// Print how long each phase of the solution took, as marked by its spans
+    #[structopt(long)]
+    profile: bool,
+    /// Write the spans to this file as a Chrome trace, for chrome://tracing or Perfetto
+    #[structopt(long)]
+    trace: Option<PathBuf>,
 }
 
 #[derive(StructOpt)]
 pub struct RunAll {
+    /// Event year, defaults to 2021
+    year: Option<Year>,
     /// End day
     #[structopt(long, short)]
     end: Option<NonZeroU8>,
-    /// Benchmark
+    /// Days to run, e.g. 3,5,10-14. Defaults to every day
     #[structopt(long, short)]
-    bench: bool,
+    days: Option<Days>,
+    /// Part to run
+    #[structopt(long, short, default_value)]
+    part: Part,
+    /// Leave out days that haven't been solved yet, instead of reporting them as failures
+    #[structopt(long, short)]
+    skip_unimplemented: bool,
+    /// How many days to solve at once, 0 uses every core. Benchmarks are cleanest with 1
+    #[structopt(long, short, default_value = "1")]
+    jobs: usize,
+    #[structopt(flatten)]
+    bench: Bench,
+    #[structopt(flatten)]
+    supervision: Supervision,
+    /// Output format: text, json, csv or markdown
+    #[structopt(long, default_value)]
+    format: Format,
 }
 
-#[derive(StructOpt)]
-enum Part {
-    Part1,
-    Part2,
-    Both,
+/// Grids are drawn on the lines below, after the letters they spell out (if they can be read)
+pub fn format_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Grid(grid) => format!("{}\n{}", grid.ocr().unwrap_or_default(), grid),
+        _ => answer.to_string(),
+    }
 }
 
-impl FromStr for Part {
-    type Err = &'static str;
+fn format_both((p1, p2): &(Answer, Answer)) -> String {
+    format!(
+        "Part1: {}\n\
+        Part2: {}",
+        format_answer(p1),
+        format_answer(p2)
+    )
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "part1" | "1" => Ok(Self::Part1),
-            "part2" | "2" => Ok(Self::Part2),
-            "both" | "b" => Ok(Self::Both),
-            _ => Err("Unknown"),
-        }
+/// `$AOC_INPUT/<year>/DayN`, where inputs live unless told otherwise. Inputs of the default
+/// year can also be straight in `$AOC_INPUT`, from before there were other years, and new
+/// ones go there too until it has a directory of its own
+pub fn input_path(day: Day) -> Result<PathBuf> {
+    let dir = env::var("AOC_INPUT").wrap_err("AOC_INPUT is not set")?;
+    let file = format!("Day{}", day.get());
+    let year_dir = Path::new(&dir).join(day.year().to_string());
+    let path = year_dir.join(&file);
+    let legacy = Path::new(&dir).join(&file);
+    if day.year() == Year::DEFAULT && !path.exists() && (legacy.exists() || !year_dir.exists()) {
+        return Ok(legacy);
     }
+    Ok(path)
 }
 
-impl Default for Part {
-    fn default() -> Self {
-        Self::Both
-    }
+/// Reads and normalizes an input file
+pub fn read_input(path: &Path) -> Result<String> {
+    let mut file =
+        File::open(path).wrap_err_with(|| format!("Could not open {}", path.display()))?;
+
+    let mut input = String::new();
+    file.read_to_string(&mut input)
+        .wrap_err_with(|| format!("Could not read {}", path.display()))?;
+    Ok(input::normalize(&input))
 }
 
-impl Display for Part {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// Where `run` gets its input from. Stdin can only be read once, so it's kept as text
+enum InputSource {
+    File(PathBuf),
+    Text(String),
+}
+
+impl InputSource {
+    fn read(&self) -> Result<String> {
         match self {
-            Part::Part1 => write!(f, "part1"),
-            Part::Part2 => write!(f, "part2"),
-            Part::Both => write!(f, "both"),
+            InputSource::File(path) => read_input(path),
+            InputSource::Text(text) => Ok(input::normalize(text)),
         }
     }
 }
 
+/// Parses `input`, along with how long that took
+pub fn parse_timed(
+    code: &dyn AoCDay,
+    day: Day,
+    input: &str,
+    params: &Params,
+) -> (aoc2021::error::Result<Parsed>, Duration) {
+    let start = Instant::now();
+    let parsed = code.parse(input, params);
+    let time = Instant::now() - start;
+    (parsed.map_err(|e| e.in_day(day.get())), time)
+}
+
+/// Solves one part for the machine-readable formats. When benchmarking, the times are the
+/// medians of the benchmark instead of a single run
+fn record(
+    bench: &Bench,
+    code: &dyn AoCDay,
+    day: Day,
+    part: u8,
+    read: impl Fn() -> Result<String>,
+    input: &str,
+    params: &Params,
+) -> Result<Record> {
+    let solve = |parsed: &Parsed| {
+        code.solve(parsed, part, params)
+            .map_err(|e| e.in_day(day.get()).in_part(part))
+    };
+    let (parsed, mut parse_time) = parse_timed(code, day, input, params);
+    let start = Instant::now();
+    let result = parsed.and_then(|parsed| solve(&parsed));
+    let mut time = Instant::now() - start;
+    if bench.bench && result.is_ok() {
+        let benchmark = bench.measure(read, |input| code.parse(input, params), solve)?;
+        parse_time = Duration::seconds_f64(benchmark.parse.median / 1_000_000.0);
+        time = Duration::seconds_f64(benchmark.solve.median / 1_000_000.0);
+    }
+    Ok(Record::new(day, part, &result, parse_time, time))
+}
+
+/// The records of a day solved by a worker, where a timeout is a failure of each part rather
+/// than an error
+fn supervised_records(
+    day: Day,
+    part: Part,
+    report: Result<Report>,
+    memory: bool,
+) -> Result<Vec<Record>> {
+    match report {
+        Ok(report) => Ok(report.records(memory)),
+        Err(e) => match e.downcast_ref::<TimedOut>() {
+            Some(timed_out) => Ok(part
+                .numbers()
+                .iter()
+                .map(|&part| Record::timed_out(day, part, timed_out))
+                .collect()),
+            None => Err(e),
+        },
+    }
+}
+
+/// `--baseline` compares tables, which only the text format prints
+fn check_format(format: Format, bench: &Bench) -> Result<()> {
+    if format != Format::Text && bench.uses_baseline() {
+        bail!("--baseline and --save-baseline only work with --format text");
+    }
+    Ok(())
+}
+
 impl Run {
-    pub fn run(&self) -> Result<(String, Option<Duration>)> {
-        let mut file;
-        if let Some(ref path) = self.input {
-            file = File::open(path)?;
-        } else {
-            let path = env::var("AOC_INPUT")?;
-            let path = Path::new(&path).join(format!("Day{}", self.day.get()));
-            file = File::open(path)?;
+    /// Returns the output, and how many parts failed. Only the machine-readable formats
+    /// report failures in the output, text fails with the error instead
+    pub fn run(&self) -> Result<(String, usize)> {
+        check_format(self.format, &self.bench)?;
+        let day = self.day.in_year(self.year);
+        let source = self.input_source(day)?;
+        let input = source.read()?;
+        let code = day.get_variant(&self.variant).map_err(|e| eyre!(e))?;
+        let params = Params::parse(code.params(), &self.extra)?;
+        self.supervision.check(&self.bench)?;
+        let profiling = self.profile || self.trace.is_some();
+        if profiling
+            && (self.bench.bench || self.supervision.enabled() || self.format != Format::Text)
+        {
+            bail!("--profile and --trace can't be combined with --bench, --timeout, --memory or --format");
+        }
+        if self.supervision.enabled() {
+            return self.run_supervised(day, &input);
         }
 
-        let mut input = String::new();
-        file.read_to_string(&mut input).unwrap();
-        let code = self.day.get_code();
-        let extra_args = &self.extra;
+        if self.format != Format::Text {
+            let records = self
+                .part
+                .numbers()
+                .iter()
+                .map(|&part| {
+                    let read = || source.read();
+                    record(&self.bench, code, day, part, read, &input, &params)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let failures = records.iter().filter(|record| record.is_failure()).count();
+            return Ok((self.format.render(&records)?, failures));
+        }
 
-        let start = Instant::now();
-        let output = match self.part {
-            Part::Part1 => code.part1(&input, extra_args),
-            Part::Part2 => code.part2(&input, extra_args),
-            Part::Both => code.both(&input, extra_args),
+        let solve = |parsed: &Parsed| {
+            let output = match self.part {
+                Part::Part1 => code
+                    .solve(parsed, 1, &params)
+                    .map(|answer| format_answer(&answer))
+                    .map_err(|e| e.in_part(1)),
+                Part::Part2 => code
+                    .solve(parsed, 2, &params)
+                    .map(|answer| format_answer(&answer))
+                    .map_err(|e| e.in_part(2)),
+                Part::Both => code
+                    .solve_both(parsed, &params)
+                    .map(|answers| format_both(&answers)),
+            };
+            output.map_err(|e| e.in_day(day.get()))
         };
+        if profiling {
+            profile::start();
+        }
+        let (parsed, _) = parse_timed(code, day, &input, &params);
+        let mut output = solve(&parsed?)?;
+        if profiling {
+            let profile = profile::finish();
+            if self.profile {
+                output.push('\n');
+                output.push_str(&bench::profile_report(&profile));
+            }
+            if let Some(ref path) = self.trace {
+                fs::write(path, profile.chrome_trace()?)
+                    .wrap_err_with(|| format!("Could not write {}", path.display()))?;
+            }
+        }
 
-        let mut duration = None;
-        if self.bench {
-            let end = Instant::now();
-            duration = Some(end.sub(start));
+        if self.bench.bench {
+            let key = bench::key(day, self.part, &self.variant);
+            let baseline = self.bench.baseline()?;
+            let parse = |input: &str| code.parse(input, &params);
+            let benchmark = self.bench.measure(|| source.read(), parse, solve)?;
+            output.push('\n');
+            output.push_str(
+                &self
+                    .bench
+                    .report(&benchmark, baseline.as_ref().and_then(|b| b.get(&key))),
+            );
+            self.bench.save(vec![(key, benchmark)])?;
         }
+        Ok((output, 0))
+    }
 
-        Ok((output, duration))
+    /// `run` in a worker, for `--timeout` and `--memory`
+    fn run_supervised(&self, day: Day, input: &str) -> Result<(String, usize)> {
+        let report = self
+            .supervision
+            .run(day, self.part, &self.variant, &self.extra, input);
+        if self.format != Format::Text {
+            let records = supervised_records(day, self.part, report, self.supervision.memory)?;
+            let failures = records.iter().filter(|record| record.is_failure()).count();
+            return Ok((self.format.render(&records)?, failures));
+        }
+
+        let report = report?;
+        let mut output = report.output(self.part == Part::Both)?;
+        if let (true, Some(kb)) = (self.supervision.memory, report.peak_memory_kb()) {
+            output.push_str(&format!("\nPeak memory: {}", format_memory(kb)));
+        }
+        Ok((output, 0))
+    }
+
+    fn input_source(&self, day: Day) -> Result<InputSource> {
+        Ok(match (&self.input, &self.input_str) {
+            (_, Some(text)) => InputSource::Text(text.clone()),
+            (Some(path), None) if path == Path::new("-") => {
+                let mut text = String::new();
+                io::stdin()
+                    .read_to_string(&mut text)
+                    .wrap_err("Could not read stdin")?;
+                InputSource::Text(text)
+            }
+            (Some(path), None) => InputSource::File(path.clone()),
+            (None, None) => InputSource::File(cached_input(day)?),
+        })
     }
+
+    /// The parameters `--extra` takes for the day, if it has any
+    pub fn params_help(&self) -> Option<String> {
+        let day = self.day.in_year(self.year);
+        let code = day.get_variant(&self.variant).ok()?;
+        if code.params().is_empty() {
+            return None;
+        }
+        let usages = code.params().iter().map(Param::usage).collect::<Vec<_>>();
+        let width = usages.iter().map(|(syntax, _)| syntax.len()).max()?;
+        let mut help = format!("PARAMETERS OF DAY {}:", day.get());
+        for (syntax, description) in usages {
+            help.push_str(&format!("\n    {:width$}    {}", syntax, description));
+        }
+        Some(help)
+    }
+
+    /// `run --watch`. Only returns if something goes wrong
+    pub fn watch(&self) -> Result<()> {
+        if self.bench.bench || self.format != Format::Text || self.supervision.enabled() {
+            bail!("--watch can't be combined with --bench, --format, --timeout or --memory");
+        }
+        let day = self.day.in_year(self.year);
+        let path = match self.input_source(day)? {
+            InputSource::File(path) => path,
+            InputSource::Text(_) => bail!("--watch needs an input file to watch"),
+        };
+        let code = day.get_variant(&self.variant).map_err(|e| eyre!(e))?;
+        let params = Params::parse(code.params(), &self.extra)?;
+        let source = solution_path(day);
+        let mut watcher = Watcher::new([path.clone(), source.clone()]);
+        let mut previous = vec![None; self.part.numbers().len()];
+        loop {
+            match read_input(&path) {
+                Ok(input) => {
+                    for (&part, previous) in self.part.numbers().iter().zip(&mut previous) {
+                        let start = Instant::now();
+                        let result = code
+                            .solve_input(&input, part, &params)
+                            .map_err(|e| e.in_day(day.get()));
+                        let time = Instant::now() - start;
+                        match result {
+                            Ok(answer) => {
+                                let answer = answer.to_submission();
+                                let change = match previous.replace(answer.clone()) {
+                                    Some(old) if old != answer => format!("{} -> {}", old, answer),
+                                    Some(_) => format!("{} (unchanged)", answer),
+                                    None => answer,
+                                };
+                                println!(
+                                    "Part{}: {} in {}μs",
+                                    part,
+                                    change,
+                                    time.whole_microseconds()
+                                );
+                            }
+                            Err(e) => println!("Error: {}", e),
+                        }
+                    }
+                }
+                Err(e) => println!("Error: {:#}", e),
+            }
+
+            let changed = watcher.wait();
+            println!();
+            if changed.contains(&source) {
+                println!("{} changed, rebuilding", source.display());
+                if let Err(e) = watch::rebuild_and_restart() {
+                    println!("Error: {:#}", e);
+                }
+            } else {
+                println!("{} changed", path.display());
+            }
+        }
+    }
+}
+
+/// What `run-all` prints about one day
+struct DayRun {
+    /// The answers
+    output: String,
+    parse_time: Duration,
+    /// Parsing and solving once, measured by the worker when there is one, as starting it
+    /// shouldn't count
+    time: Duration,
+    peak_memory_kb: Option<u64>,
+    benchmark: Option<Benchmark>,
 }
 
 impl RunAll {
-    pub fn run_all(&self) -> Result<(String, Option<Duration>)> {
+    /// Runs every day up to `end`. A failing day is reported in the output, along with
+    /// the number of failed days, instead of stopping the run
+    pub fn run_all(&self) -> Result<(String, usize)> {
+        check_format(self.format, &self.bench)?;
+        self.supervision.check(&self.bench)?;
+        if self.format != Format::Text {
+            return self.records();
+        }
+
         let mut output: Vec<String> = vec![];
+        let mut failures = 0;
+        let baseline = self.bench.baseline()?;
+        let mut benchmarks = vec![];
         let start = Instant::now();
-        let end = self
-            .end
-            .unwrap_or_else(|| NonZeroU8::new(25).unwrap())
-            .into();
-        for day in 1..=end {
-            output.push(format!("Day {}", day));
-            let day = NonZeroU8::new(day).unwrap();
-            let day = Day { day };
-            let path = env::var("AOC_INPUT")?;
-            let path = Path::new(&path).join(format!("Day{}", day.day));
-            let mut file = File::open(path)?;
-
-            let mut input = String::new();
-            file.read_to_string(&mut input).unwrap();
-            let code = day.get_code();
-            let extra_args = vec![];
-
-            let start = Instant::now();
-            let result = code.both(&input, &extra_args);
-            let end = Instant::now();
-            let duration = end.sub(start);
-            if self.bench {
-                output.push(format!("Time: {}μs", duration.whole_microseconds()));
-            }
+        let results = self.for_each_day(|day| self.run_day(day))?;
+        let duration = Instant::now() - start;
+        for (day, result) in results {
+            output.push(format!("Day {}", day.get()));
 
-            output.push(result);
+            match result {
+                Ok(run) => {
+                    output.push(run.output);
+                    let mut times = format!(
+                        "Time: {}μs (parsing {}μs)",
+                        run.time.whole_microseconds(),
+                        run.parse_time.whole_microseconds()
+                    );
+                    if let Some(kb) = run.peak_memory_kb {
+                        times.push_str(&format!(", peak memory {}", format_memory(kb)));
+                    }
+                    output.push(times);
+                    if let Some(benchmark) = run.benchmark {
+                        let key = bench::key(day, self.part, registry::DEFAULT_VARIANT);
+                        output.push(
+                            self.bench
+                                .report(&benchmark, baseline.as_ref().and_then(|b| b.get(&key))),
+                        );
+                        benchmarks.push((key, benchmark));
+                    }
+                }
+                Err(e) => {
+                    failures += 1;
+                    match e.downcast_ref::<TimedOut>() {
+                        Some(timed_out) => output.push(timed_out.to_string()),
+                        None => output.push(format!("Error: {:#}", e)),
+                    }
+                }
+            }
             output.push("".to_string());
         }
 
-        let mut duration = None;
-        if self.bench {
-            let end = Instant::now();
-            duration = Some(end.sub(start));
+        output.push(format!("Total time: {:.6}s", duration.as_seconds_f64()));
+        self.bench.save(benchmarks)?;
+
+        Ok((output.join("\n"), failures))
+    }
+
+    fn days(&self) -> Vec<Day> {
+        let end = self.end.map_or(25, NonZeroU8::get);
+        let days = self.days.clone().unwrap_or_else(|| "1-25".parse().unwrap());
+        days.iter(self.year.unwrap_or_default())
+            .filter(|day| day.get() <= end)
+            .filter(|day| !self.skip_unimplemented || day.get_code().implemented())
+            .collect()
+    }
+
+    /// Runs `f` for every day, `jobs` days at a time. The results are in day order however
+    /// many jobs there are
+    fn for_each_day<T: Send>(&self, f: impl Fn(Day) -> T + Sync) -> Result<Vec<(Day, T)>> {
+        let days = self.days();
+        let run = |day| (day, f(day));
+        if self.jobs == 1 {
+            return Ok(days.into_iter().map(run).collect());
+        }
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.jobs)
+            .build()?;
+        Ok(pool.install(|| days.into_par_iter().map(run).collect()))
+    }
+
+    /// `run_all` for the machine-readable formats
+    fn records(&self) -> Result<(String, usize)> {
+        let results = self.for_each_day(|day| -> Result<Vec<Record>> {
+            let input = cached_input(day).and_then(|path| Ok((read_input(&path)?, path)));
+            let code = day.get_code();
+            let params = Params::new(code.params());
+            if self.supervision.enabled() {
+                let (part, memory) = (self.part, self.supervision.memory);
+                return match input {
+                    Ok((ref input, _)) => {
+                        let report = self.supervision.run(day, part, DEFAULT_VARIANT, &[], input);
+                        Ok(
+                            supervised_records(day, part, report, memory).unwrap_or_else(|e| {
+                                let failed = |&part| Record::failed(day, part, format!("{:#}", e));
+                                part.numbers().iter().map(failed).collect()
+                            }),
+                        )
+                    }
+                    Err(ref e) => Ok(part
+                        .numbers()
+                        .iter()
+                        .map(|&part| Record::failed(day, part, format!("{:#}", e)))
+                        .collect()),
+                };
+            }
+            self.part
+                .numbers()
+                .iter()
+                .map(|&part| match input {
+                    Ok((ref input, ref path)) => {
+                        let read = || read_input(path);
+                        record(&self.bench, code, day, part, read, input, &params)
+                    }
+                    Err(ref e) => Ok(Record::failed(day, part, format!("{:#}", e))),
+                })
+                .collect()
+        })?;
+        let mut records = vec![];
+        for (_, result) in results {
+            records.extend(result?);
+        }
+        let failures = records
+            .iter()
+            .filter(|record| record.is_failure())
+            .map(|record| record.day)
+            .collect::<BTreeSet<_>>()
+            .len();
+        Ok((self.format.render(&records)?, failures))
+    }
+
+    fn run_day(&self, day: Day) -> Result<DayRun> {
+        let path = cached_input(day)?;
+        let input = read_input(&path)?;
+        if self.supervision.enabled() {
+            let report = self
+                .supervision
+                .run(day, self.part, DEFAULT_VARIANT, &[], &input)?;
+            let (parse_time, time) = report.times();
+            return Ok(DayRun {
+                output: report.output(true)?,
+                parse_time,
+                time,
+                peak_memory_kb: report.peak_memory_kb().filter(|_| self.supervision.memory),
+                benchmark: None,
+            });
         }
+        let code = day.get_code();
+        let params = Params::new(code.params());
 
-        Ok((output.join("\n"), duration))
+        let solve_day = |parsed: &Parsed| {
+            let output = match self.part {
+                Part::Both => code
+                    .solve_both(parsed, &params)
+                    .map(|answers| format_both(&answers)),
+                part => {
+                    let part = part.numbers()[0];
+                    code.solve(parsed, part, &params)
+                        .map(|answer| format!("Part{}: {}", part, format_answer(&answer)))
+                        .map_err(|e| e.in_part(part))
+                }
+            };
+            output.map_err(|e| e.in_day(day.get()))
+        };
+        let (parsed, parse_time) = parse_timed(code, day, &input, &params);
+        let parsed = parsed?;
+        let start = Instant::now();
+        let result = solve_day(&parsed)?;
+        let time = parse_time + (Instant::now() - start);
+        let benchmark = match self.bench.bench {
+            true => {
+                let parse = |input: &str| code.parse(input, &params);
+                Some(self.bench.measure(|| read_input(&path), parse, solve_day)?)
+            }
+            false => None,
+        };
+        Ok(DayRun {
+            output: result,
+            parse_time,
+            time,
+            peak_memory_kb: None,
+            benchmark,
+        })
     }
 }
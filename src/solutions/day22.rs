@@ -1,15 +0,0 @@
-#![allow(unused_variables)]
-
-use crate::AoCDay;
-
-pub struct Code;
-
-impl AoCDay for Code {
-    fn part1(&self, input: &str, _extra_args: &[String]) -> String {
-        todo!()
-    }
-
-    fn part2(&self, input: &str, _extra_args: &[String]) -> String {
-        todo!()
-    }
-}
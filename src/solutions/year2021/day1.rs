@@ -0,0 +1,62 @@
+use crate::answer::Answer;
+use crate::error::{LineContext, Result};
+use crate::params::Params;
+use crate::Solution;
+
+pub struct Code;
+
+crate::register!(2021, 1);
+
+impl Solution for Code {
+    type Parsed = Vec<u64>;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Vec<u64>> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| line.trim().parse::<u64>().at_line(index + 1, line))
+            .collect()
+    }
+
+    fn part1(&self, depths: &Vec<u64>, _params: &Params) -> Result<Answer> {
+        let mut previous = None;
+        let mut increases: u64 = 0;
+        for &current in depths {
+            match previous {
+                Some(previous) if current > previous => increases += 1,
+                _ => (),
+            }
+            previous = Some(current);
+        }
+        Ok(increases.into())
+    }
+
+    fn part2(&self, depths: &Vec<u64>, _params: &Params) -> Result<Answer> {
+        if depths.len() < 3 {
+            // Not even one full window, so nothing can increase
+            return Ok(0.into());
+        }
+
+        // Basically, keep a rolling window of the past three numbers,
+        // kinda like a ring buffer. `window_index` tracks the oldest
+        // entry, allowing us to swap in the newest entry. This is
+        // a bit verbose, but maybe a bit faster than looking behind
+        // us in the vec every time.
+        let mut increases: u64 = 0;
+        let mut window_index = 0;
+        let mut window = [depths[0], depths[1], depths[2]];
+        let mut window_total = depths[0] + depths[1] + depths[2];
+        for &num in &depths[3..] {
+            let current_total = window_total - window[window_index] + num;
+            if current_total > window_total {
+                increases += 1;
+            }
+            window[window_index] = num;
+            window_index += 1;
+            window_index %= 3;
+            window_total = current_total;
+        }
+
+        Ok(increases.into())
+    }
+}
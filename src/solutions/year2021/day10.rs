@@ -0,0 +1,115 @@
+#![allow(unused_variables)]
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::params::Params;
+use crate::Solution;
+
+pub struct Code;
+
+crate::register!(2021, 10);
+
+/// Lines are only ever made of brackets, so the rest of the code doesn't have to check
+fn parse_line(index: usize, line: &str) -> Result<String> {
+    match line.chars().find(|c| !"()[]{}<>".contains(*c)) {
+        Some(c) => Err(Error::parse(
+            index + 1,
+            line,
+            format!("unexpected character {:?}", c),
+        )),
+        None => Ok(line.to_string()),
+    }
+}
+
+fn line_is_corrupted(line: &str) -> i32 {
+    let mut stack = vec![];
+    for c in line.chars() {
+        if c == '(' || c == '[' || c == '{' || c == '<' {
+            stack.push(c);
+        } else {
+            // A closer with nothing left to close is as illegal as a mismatched one
+            let matching = stack.pop().unwrap_or(' ');
+            match (matching, c) {
+                ('(', ')') => {}
+                ('[', ']') => {}
+                ('{', '}') => {}
+                ('<', '>') => {}
+                (_, ')') => return 3,
+                (_, ']') => return 57,
+                (_, '}') => return 1197,
+                (_, '>') => return 25137,
+                _ => unreachable!(),
+            }
+        }
+    }
+    0
+}
+
+fn line_is_incomplete(line: &str) -> i64 {
+    let mut stack = vec![];
+    for c in line.chars() {
+        if c == '(' || c == '[' || c == '{' || c == '<' {
+            stack.push(c);
+        } else {
+            // A closer with nothing left to close is as illegal as a mismatched one
+            let matching = stack.pop().unwrap_or(' ');
+            match (matching, c) {
+                ('(', ')') => {}
+                ('[', ']') => {}
+                ('{', '}') => {}
+                ('<', '>') => {}
+                (_, ')') => return 0,
+                (_, ']') => return 0,
+                (_, '}') => return 0,
+                (_, '>') => return 0,
+                _ => unreachable!(),
+            }
+        }
+    }
+    let mut tot: i64 = 0;
+    for next in stack.iter().rev() {
+        tot *= 5;
+        tot += match next {
+            '(' => 1,
+            '[' => 2,
+            '{' => 3,
+            '<' => 4,
+            _ => unreachable!(),
+        }
+    }
+    tot
+}
+
+impl Solution for Code {
+    type Parsed = Vec<String>;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Vec<String>> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| parse_line(index, line))
+            .collect()
+    }
+
+    fn part1(&self, lines: &Vec<String>, _params: &Params) -> Result<Answer> {
+        let answer = lines
+            .iter()
+            .map(|line| line_is_corrupted(line))
+            .sum::<i32>();
+        Ok(answer.into())
+    }
+
+    fn part2(&self, lines: &Vec<String>, _params: &Params) -> Result<Answer> {
+        let mut scores = lines
+            .iter()
+            .map(|line| line_is_incomplete(line))
+            .filter(|&x| x != 0)
+            .collect::<Vec<_>>();
+        scores.sort_unstable();
+
+        let answer = *scores
+            .get(scores.len() / 2)
+            .ok_or_else(|| Error::no_solution("there are no incomplete lines"))?;
+
+        Ok(answer.into())
+    }
+}
@@ -2,38 +2,67 @@
 
 use hashbrown::HashSet;
 
-use crate::AoCDay;
+use std::str::FromStr;
+
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::params::{Param, Params};
+use crate::Solution;
 
 pub struct Code;
 
-#[derive(Debug)]
-struct Mapping {
+crate::register!(2021, 11);
+
+const PARAMS: &[Param] =
+    &[Param::int("steps", "Steps to count the flashes of in part 1", 100).range(0, 100_000)];
+
+#[derive(Debug, Clone)]
+pub struct Mapping {
     map: Vec<i32>,
     x_size: usize,
     y_size: usize,
     flashes: i64,
 }
 
-impl Mapping {
+impl FromStr for Mapping {
+    type Err = Error;
+
     #[inline(always)]
-    fn from_str(input: &str) -> Self {
+    fn from_str(input: &str) -> Result<Self> {
         let mut map = Vec::with_capacity(10 * 10);
         let mut y_size = 0;
         let mut x_size = 0;
         for (y, line) in input.lines().enumerate() {
             y_size = y_size.max(y + 1);
+            if y > 0 && line.len() != x_size {
+                return Err(Error::parse(
+                    y + 1,
+                    line,
+                    format!("expected {} digits", x_size),
+                ));
+            }
             for (x, num) in line.chars().enumerate() {
                 x_size = x_size.max(x + 1);
-                map.push(num.to_string().parse::<i32>().unwrap());
+                let num = num.to_digit(10).ok_or_else(|| {
+                    Error::parse(y + 1, line, format!("{:?} is not a digit", num))
+                })?;
+                map.push(num as i32);
             }
         }
-        Mapping {
+        // Part 2 waits for every octopus to flash at once, which never happens without any
+        if map.is_empty() {
+            return Err(Error::no_solution("there are no octopuses"));
+        }
+        Ok(Mapping {
             map,
             x_size,
             y_size,
             flashes: 0,
-        }
+        })
     }
+}
+
+impl Mapping {
     #[inline(always)]
     fn get(&self, x: usize, y: usize) -> Option<i32> {
         if x >= self.x_size || y >= self.y_size {
@@ -52,8 +81,7 @@ impl Mapping {
     fn flash(&mut self, to_visit: &mut Vec<(usize, usize)>) -> bool {
         let mut flashed = HashSet::new();
 
-        while !to_visit.is_empty() {
-            let (x, y) = to_visit.pop().unwrap();
+        while let Some((x, y)) = to_visit.pop() {
             if let Some(n) = self.get(x, y) {
                 if n > 9 && !flashed.contains(&(x, y)) {
                     self.flashes += 1;
@@ -109,27 +137,35 @@ impl Mapping {
     }
 }
 
-impl AoCDay for Code {
-    fn part1(&self, input: &str, _extra_args: &[String]) -> String {
-        let mut map = Mapping::from_str(input);
+impl Solution for Code {
+    type Parsed = Mapping;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Mapping> {
+        Mapping::from_str(input)
+    }
+
+    fn part1(&self, map: &Mapping, params: &Params) -> Result<Answer> {
+        let mut map = map.clone();
 
-        for _ in 0..100 {
+        for _ in 0..params.int("steps") {
             map.step();
         }
         let answer = map.flashes;
-        debug_assert_eq!(answer, 1725);
-        format!("{}", answer) // 1725/410μs
+        Ok(answer.into())
     }
 
-    fn part2(&self, input: &str, _extra_args: &[String]) -> String {
-        let mut map = Mapping::from_str(input);
+    fn part2(&self, map: &Mapping, _params: &Params) -> Result<Answer> {
+        let mut map = map.clone();
 
         let mut answer = 1;
         while !map.step() {
             answer += 1;
         }
 
-        debug_assert_eq!(answer, 308);
-        format!("{}", answer) // 1725/1100μs
+        Ok(answer.into())
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
     }
 }
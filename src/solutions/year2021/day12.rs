@@ -1,4 +1,7 @@
-use crate::AoCDay;
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::params::Params;
+use crate::Solution;
 
 use hashbrown::HashMap;
 use hashbrown::HashSet;
@@ -8,6 +11,8 @@ use std::hash::Hasher;
 
 pub struct Code;
 
+crate::register!(2021, 12);
+
 struct HashWrapper<T>(HashSet<T>);
 
 impl<T> PartialEq for HashWrapper<T>
@@ -106,36 +111,43 @@ fn solve(
 }
 
 #[inline(always)]
-fn map_from_input(input: &str) -> HashMap<String, Vec<String>> {
+fn map_from_input(input: &str) -> Result<HashMap<String, Vec<String>>> {
     let mut map: HashMap<String, Vec<String>> = HashMap::new();
-    for line in input.lines() {
-        let line = line.split('-').collect::<Vec<_>>();
+    for (index, line) in input.lines().enumerate() {
+        let (from, to) = line
+            .split_once('-')
+            .ok_or_else(|| Error::parse(index + 1, line, "expected cave-cave"))?;
 
-        let entry = map.entry(line[0].to_string()).or_insert(vec![]);
-        entry.push(line[1].to_string());
+        let entry = map.entry(from.to_string()).or_insert(vec![]);
+        entry.push(to.to_string());
 
-        let entry = map.entry(line[1].to_string()).or_insert(vec![]);
-        entry.push(line[0].to_string());
+        let entry = map.entry(to.to_string()).or_insert(vec![]);
+        entry.push(from.to_string());
+    }
+    if !map.contains_key("start") {
+        return Err(Error::no_solution("there is no start cave"));
     }
-    map
+    Ok(map)
 }
 
-impl AoCDay for Code {
-    fn part1(&self, input: &str, _extra_args: &[String]) -> String {
-        let map = map_from_input(input);
+impl Solution for Code {
+    type Parsed = HashMap<String, Vec<String>>;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<HashMap<String, Vec<String>>> {
+        map_from_input(input)
+    }
+
+    fn part1(&self, map: &HashMap<String, Vec<String>>, _params: &Params) -> Result<Answer> {
         let mut memo = HashMap::with_capacity(5000);
-        let answer = solve(&map, &HashWrapper::new(), "start", true, &mut memo);
+        let answer = solve(map, &HashWrapper::new(), "start", true, &mut memo);
 
-        debug_assert_eq!(answer, 5076);
-        format!("{}", answer) // 5076/~5000μs
+        Ok(answer.into())
     }
 
-    fn part2(&self, input: &str, _extra_args: &[String]) -> String {
-        let map = map_from_input(input);
+    fn part2(&self, map: &HashMap<String, Vec<String>>, _params: &Params) -> Result<Answer> {
         let mut memo = HashMap::with_capacity(5000);
-        let answer = solve(&map, &HashWrapper::new(), "start", false, &mut memo);
+        let answer = solve(map, &HashWrapper::new(), "start", false, &mut memo);
 
-        debug_assert_eq!(answer, 145643);
-        format!("{}", answer) // 145643/~20000μs
+        Ok(answer.into())
     }
 }
@@ -1,13 +1,21 @@
-use nom::{branch::alt, bytes::complete::tag, character::complete::digit1, IResult};
+use nom::{
+    branch::alt, bytes::complete::tag, character::complete::digit1, combinator::map_res, IResult,
+};
 
 use hashbrown::HashSet;
 
-use crate::AoCDay;
+use crate::answer::{Answer, Grid};
+use crate::error::{Error, LineContext, Result};
+use crate::params::Params;
+use crate::profile;
+use crate::Solution;
 
 pub struct Code;
 
+crate::register!(2021, 13);
+
 #[derive(Debug)]
-enum Fold {
+pub enum Fold {
     X(i32),
     Y(i32),
 }
@@ -17,8 +25,7 @@ fn parse_fold(input: &str) -> IResult<&str, Fold> {
     let (input, _) = tag("fold along ")(input)?;
     let (input, axis) = alt((tag("x"), tag("y")))(input)?;
     let (input, _) = tag("=")(input)?;
-    let (input, num) = digit1(input)?;
-    let num = num.trim().parse::<i32>().unwrap();
+    let (input, num) = map_res(digit1, |n: &str| n.parse::<i32>())(input)?;
     match axis {
         "x" => Ok((input, Fold::X(num))),
         "y" => Ok((input, Fold::Y(num))),
@@ -26,8 +33,8 @@ fn parse_fold(input: &str) -> IResult<&str, Fold> {
     }
 }
 
-#[derive(Debug)]
-struct Mapping {
+#[derive(Debug, Clone)]
+pub struct Mapping {
     map: HashSet<(i32, i32)>,
     max_x: i32,
     max_y: i32,
@@ -68,66 +75,74 @@ impl Mapping {
         }
     }
     #[inline(always)]
-    fn print(&self) -> String {
-        let mut s = vec!['\n'];
-        for y in 0..=self.max_y {
-            for x in 0..=self.max_x {
-                if self.map.contains(&(x, y)) {
-                    s.push('#');
-                } else {
-                    s.push(' ');
-                }
-            }
-            s.push('\n');
+    fn to_grid(&self) -> Grid {
+        let mut grid = Grid::new(self.max_x as usize + 1, self.max_y as usize + 1);
+        for (x, y) in self.map.iter() {
+            grid.set(*x as usize, *y as usize);
         }
-        s.iter().collect::<String>()
+        grid
     }
 }
 
 #[inline(always)]
-fn get_map_and_folds_from_input(input: &str) -> (Mapping, Vec<Fold>) {
+fn get_map_and_folds_from_input(input: &str) -> Result<(Mapping, Vec<Fold>)> {
     let mut first_part = true;
     let mut map = HashSet::new();
     let mut folds = vec![];
     let mut max_x = 0;
     let mut max_y = 0;
-    for line in input.lines() {
+    for (index, line) in input.lines().enumerate() {
         if line.is_empty() {
             first_part = false;
             continue;
         }
         if first_part {
-            let parts = line.split(',').collect::<Vec<_>>();
-            let x = parts[0].parse::<i32>().unwrap();
-            let y = parts[1].parse::<i32>().unwrap();
+            let (x, y) = line
+                .split_once(',')
+                .ok_or_else(|| Error::parse(index + 1, line, "expected x,y"))?;
+            let x = x.parse::<i32>().at_line(index + 1, line)?;
+            let y = y.parse::<i32>().at_line(index + 1, line)?;
             map.insert((x, y));
             max_x = max_x.max(x);
             max_y = max_y.max(y);
         } else {
-            let (_, fold) = parse_fold(line).unwrap();
+            let (_, fold) = parse_fold(line).at_line(index + 1, line)?;
             folds.push(fold);
         }
     }
     let map = Mapping { map, max_x, max_y };
-    (map, folds)
+    Ok((map, folds))
 }
 
-impl AoCDay for Code {
-    fn part1(&self, input: &str, _extra_args: &[String]) -> String {
-        let (mut map, folds) = get_map_and_folds_from_input(input);
-        map.fold(&folds[0]);
+impl Solution for Code {
+    type Parsed = (Mapping, Vec<Fold>);
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<(Mapping, Vec<Fold>)> {
+        get_map_and_folds_from_input(input)
+    }
+
+    fn part1(&self, (map, folds): &(Mapping, Vec<Fold>), _params: &Params) -> Result<Answer> {
+        let mut map = map.clone();
+        let fold = folds
+            .first()
+            .ok_or_else(|| Error::no_solution("there are no folds"))?;
+        {
+            let _span = profile::span("fold");
+            map.fold(fold);
+        }
         let answer = map.map.len();
 
-        debug_assert_eq!(answer, 724);
-        format!("{}", answer) // 724/~340μs
+        Ok(answer.into())
     }
 
-    fn part2(&self, input: &str, _extra_args: &[String]) -> String {
-        let (mut map, folds) = get_map_and_folds_from_input(input);
+    fn part2(&self, (map, folds): &(Mapping, Vec<Fold>), _params: &Params) -> Result<Answer> {
+        let mut map = map.clone();
         for f in folds {
-            map.fold(&f);
+            let _span = profile::span("fold");
+            map.fold(f);
         }
 
-        map.print() // CPJBERUL/~460μs
+        let _span = profile::span("draw");
+        Ok(map.to_grid().into())
     }
 }
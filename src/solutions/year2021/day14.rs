@@ -0,0 +1,132 @@
+#![allow(unused_variables)]
+
+use hashbrown::HashMap;
+
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::params::{Param, Params};
+use crate::Solution;
+
+pub struct Code;
+
+crate::register!(2021, 14);
+
+const PARAMS: &[Param] = &[Param::int("steps", "Insertion steps", 10)
+    .part2(40)
+    .range(0, 1_000)];
+
+/// Counts of the pairs and of the elements in the template, and the insertion rules
+pub struct Polymer {
+    template: HashMap<(char, char), i128>,
+    counts: HashMap<char, i128>,
+    pairs: HashMap<(char, char), char>,
+}
+
+fn parse_polymer(input: &str) -> Result<Polymer> {
+    let mut template = HashMap::new();
+    let mut pairs = HashMap::new();
+    let mut counts = HashMap::new();
+    for (index, line) in input.lines().enumerate() {
+        if template.is_empty() {
+            for char in line.chars() {
+                let entry = counts.entry(char).or_insert(0);
+                *entry += 1;
+            }
+            for chars in line.chars().zip(line.chars().skip(1)) {
+                let entry = template.entry(chars).or_insert(0);
+                *entry += 1;
+            }
+        } else if line.is_empty() {
+            continue;
+        } else {
+            let rule = line.split_once(" -> ").map(|(pair, insert)| {
+                (
+                    pair.chars().collect::<Vec<_>>(),
+                    insert.chars().collect::<Vec<_>>(),
+                )
+            });
+            match rule {
+                Some((pair, insert)) if pair.len() == 2 && insert.len() == 1 => {
+                    pairs.insert((pair[0], pair[1]), insert[0]);
+                }
+                _ => return Err(Error::parse(index + 1, line, "expected AB -> C")),
+            }
+        }
+    }
+    Ok(Polymer {
+        template,
+        counts,
+        pairs,
+    })
+}
+
+#[inline(always)]
+fn solve(polymer: &Polymer, iterations: i64) -> Result<i128> {
+    let pairs = &polymer.pairs;
+    let mut template = polymer.template.clone();
+    let mut counts = polymer.counts.clone();
+    let too_many = || {
+        Error::argument(format!(
+            "the polymer is too long after {} steps",
+            iterations
+        ))
+    };
+    for i in 0..iterations {
+        let mut next = template.clone();
+        for (p @ (c1, c2), val) in template.iter() {
+            if let Some(insert) = pairs.get(p) {
+                let entry = next.entry(*p).or_insert(0);
+                *entry -= val;
+
+                let entry = next.entry((*c1, *insert)).or_insert(0);
+                *entry = entry.checked_add(*val).ok_or_else(too_many)?;
+
+                let entry = next.entry((*insert, *c2)).or_insert(0);
+                *entry = entry.checked_add(*val).ok_or_else(too_many)?;
+
+                let entry = counts.entry(*insert).or_insert(0);
+                *entry = entry.checked_add(*val).ok_or_else(too_many)?;
+            }
+        }
+        template = next;
+    }
+
+    if counts.is_empty() {
+        return Err(Error::no_solution("there is no polymer template"));
+    }
+
+    let mut most = 0;
+    let mut least = i128::MAX;
+    for (c, n) in counts {
+        if n > most {
+            most = n;
+        }
+        if n < least {
+            least = n;
+        }
+    }
+
+    Ok(most - least)
+}
+
+impl Solution for Code {
+    type Parsed = Polymer;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Polymer> {
+        parse_polymer(input)
+    }
+
+    fn part1(&self, polymer: &Polymer, params: &Params) -> Result<Answer> {
+        let answer = solve(polymer, params.int_in("steps", 1))?;
+        Ok(answer.into()) // 2590
+    }
+
+    fn part2(&self, polymer: &Polymer, params: &Params) -> Result<Answer> {
+        let answer = solve(polymer, params.int_in("steps", 2))?;
+        Ok(answer.into()) // 2875665202438/
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+}
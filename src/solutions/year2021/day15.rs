@@ -0,0 +1,330 @@
+use hashbrown::HashMap;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::params::{Param, Params};
+use crate::profile;
+use crate::Solution;
+
+pub struct Code;
+
+crate::register!(2021, 15);
+
+/// Dijkstra over a flat grid, instead of hash maps
+pub struct Grid;
+
+crate::register!(2021, 15, "grid", Grid);
+
+const PARAMS: &[Param] = &[Param::int(
+    "tiles",
+    "How many times the cave repeats in each direction",
+    1,
+)
+.part2(5)
+.range(1, 100)];
+
+type Coord = (usize, usize);
+
+struct Mapping {
+    map: HashMap<Coord, i32>,
+    x_size: usize,
+    y_size: usize,
+}
+
+#[derive(PartialEq, Eq)]
+struct Node {
+    coords: Coord,
+    weight: i32,
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.weight.cmp(&other.weight)
+    }
+}
+
+impl Mapping {
+    #[inline(always)]
+    fn from_map(map: &HashMap<Coord, i32>, x_size: usize, y_size: usize) -> Self {
+        let mut new_map = HashMap::new();
+        for (coords, weight) in map {
+            new_map.insert(*coords, *weight);
+        }
+        Mapping {
+            map: new_map,
+            x_size,
+            y_size,
+        }
+    }
+    #[inline(always)]
+    fn neighbors(&self, x: usize, y: usize) -> Vec<(Coord, i32)> {
+        let mut neighbors = vec![];
+        if x > 0 {
+            neighbors.push(((x - 1, y), *self.map.get(&(x - 1, y)).unwrap()));
+        }
+        if y > 0 {
+            neighbors.push(((x, y - 1), *self.map.get(&(x, y - 1)).unwrap()));
+        }
+        if x < self.x_size - 1 {
+            neighbors.push(((x + 1, y), *self.map.get(&(x + 1, y)).unwrap()));
+        }
+        if y < self.y_size - 1 {
+            neighbors.push(((x, y + 1), *self.map.get(&(x, y + 1)).unwrap()));
+        }
+        neighbors
+    }
+    #[inline(always)]
+    fn solve(&self) -> i32 {
+        let Mapping {
+            map,
+            x_size,
+            y_size,
+        } = self;
+        let mut distances = HashMap::new();
+        let mut unvisited = HashMap::new();
+        let mut unvisited_heap = BinaryHeap::new();
+        let total_size = x_size * y_size;
+        let mut visited_size = 0;
+        let adjacency = profile::span("adjacency");
+        for ((x, y), _) in map.iter() {
+            for ((n_x, n_y), weight) in self.neighbors(*x, *y) {
+                let entry = distances.entry((*x, *y)).or_insert(vec![]);
+                entry.push(((n_x, n_y), weight));
+            }
+        }
+        drop(adjacency);
+        let _span = profile::span("dijkstra");
+        let mut visited = HashMap::new();
+        let mut current = (0, 0);
+        let mut current_distance = 0;
+        unvisited_heap.push(Reverse(Node {
+            coords: current,
+            weight: current_distance,
+        }));
+        unvisited.insert(current, current_distance);
+
+        'outer: loop {
+            // A cave of one position has no neighbours, and is solved right away
+            for ((x, y), distance) in distances.get(&current).into_iter().flatten() {
+                let neighbor = (*x, *y);
+                if visited.contains_key(&neighbor.clone()) {
+                    continue;
+                }
+                let new_distance = current_distance + distance;
+                let unvisited_neighbor = *unvisited.get(&neighbor).unwrap_or(&i32::MAX);
+                if unvisited_neighbor == i32::MAX || unvisited_neighbor > new_distance {
+                    unvisited.insert(neighbor, new_distance);
+                    unvisited_heap.push(Reverse(Node {
+                        coords: neighbor,
+                        weight: new_distance,
+                    }));
+                }
+            }
+            visited.insert(current, current_distance);
+            visited_size += 1;
+            unvisited.remove(&current);
+            if visited_size == total_size || current == (x_size - 1, y_size - 1) {
+                break 'outer;
+            }
+            let next;
+            loop {
+                let possible_next = unvisited_heap.pop().unwrap();
+                if unvisited.contains_key(&possible_next.0.coords) {
+                    next = (possible_next.0.coords, possible_next.0.weight);
+                    break;
+                }
+            }
+            current = next.0;
+            current_distance = next.1;
+        }
+
+        current_distance
+    }
+}
+
+fn get_map_from_input(input: &str) -> Result<RiskMap> {
+    let mut x_size = 0;
+    let mut y_size = 0;
+    let mut map = HashMap::new();
+    for (y, line) in input.lines().enumerate() {
+        // Every row is as wide as the first one
+        if y == 0 {
+            x_size = line.chars().count();
+        }
+        if line.is_empty() {
+            return Err(Error::parse(y + 1, line, "the row is empty"));
+        }
+        if line.chars().count() != x_size {
+            return Err(Error::parse(
+                y + 1,
+                line,
+                format!("expected {} digits", x_size),
+            ));
+        }
+        for (x, c) in line.chars().enumerate() {
+            let risk = c
+                .to_digit(10)
+                .filter(|risk| (1..=9).contains(risk))
+                .ok_or_else(|| {
+                    Error::parse(y + 1, line, format!("{:?} is not a risk from 1 to 9", c))
+                })?;
+            map.insert((x, y), risk as i32);
+        }
+        y_size = y + 1;
+    }
+    if map.is_empty() {
+        return Err(Error::no_solution("the cave is empty"));
+    }
+    Ok((map, x_size, y_size))
+}
+
+/// Risk levels by position, and the width and height of the cave
+type RiskMap = (HashMap<Coord, i32>, usize, usize);
+
+fn lowest_risk_tiled((map, x_size, y_size): &RiskMap, tiles: usize) -> i32 {
+    let tiling = profile::span("tile");
+    let (mut map, x_size, y_size) = (map.clone(), *x_size, *y_size);
+
+    for n_x in 0..tiles {
+        for n_y in 0..tiles {
+            if n_x == 0 && n_y == 0 {
+                continue;
+            }
+            for x in 0..x_size {
+                for y in 0..y_size {
+                    let curr = map.get(&(x, y)).unwrap();
+                    let curr = (curr + (n_x + n_y) as i32 - 1) % 9 + 1;
+                    let new_x = x + (x_size * n_x);
+                    let new_y = y + (y_size * n_y);
+                    map.insert((new_x, new_y), curr);
+                }
+            }
+        }
+    }
+    let x_size = x_size * tiles;
+    let y_size = y_size * tiles;
+
+    let map = Mapping::from_map(&map, x_size, y_size);
+    drop(tiling);
+
+    map.solve()
+}
+
+impl Solution for Code {
+    type Parsed = RiskMap;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<RiskMap> {
+        get_map_from_input(input)
+    }
+
+    fn part1(&self, map: &RiskMap, params: &Params) -> Result<Answer> {
+        let tiles = params.int_in("tiles", 1) as usize;
+        let answer = lowest_risk_tiled(map, tiles);
+        Ok(answer.into())
+    }
+
+    fn part2(&self, map: &RiskMap, params: &Params) -> Result<Answer> {
+        let tiles = params.int_in("tiles", 2) as usize;
+        let answer = lowest_risk_tiled(map, tiles);
+        Ok(answer.into())
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+}
+
+/// Risk levels in row order, which repeat in both directions with the risk going up by one
+/// per tile
+pub struct Cave {
+    risks: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+impl Cave {
+    fn new(input: &str) -> Result<Self> {
+        let (map, width, height) = get_map_from_input(input)?;
+        let mut risks = vec![0; width * height];
+        for ((x, y), risk) in map {
+            risks[x + y * width] = risk as u8;
+        }
+        Ok(Cave {
+            risks,
+            width,
+            height,
+        })
+    }
+
+    #[inline(always)]
+    fn risk(&self, x: usize, y: usize) -> u32 {
+        let risk = self.risks[x % self.width + (y % self.height) * self.width] as usize;
+        let risk = risk + x / self.width + y / self.height;
+        ((risk - 1) % 9 + 1) as u32
+    }
+
+    /// From the top left to the bottom right of the cave repeated `tiles` times
+    fn lowest_risk(&self, tiles: usize) -> u32 {
+        let _span = profile::span("dijkstra");
+        let width = self.width * tiles;
+        let height = self.height * tiles;
+        let end = width * height - 1;
+        let mut distances = vec![u32::MAX; width * height];
+        let mut heap = BinaryHeap::new();
+        distances[0] = 0;
+        heap.push(Reverse((0, 0)));
+        while let Some(Reverse((distance, index))) = heap.pop() {
+            if index == end {
+                return distance;
+            }
+            if distance > distances[index] {
+                continue;
+            }
+            let (x, y) = (index % width, index / width);
+            let neighbors = [
+                (x > 0).then(|| index - 1),
+                (x + 1 < width).then(|| index + 1),
+                (y > 0).then(|| index - width),
+                (y + 1 < height).then(|| index + width),
+            ];
+            for neighbor in neighbors.into_iter().flatten() {
+                let new_distance = distance + self.risk(neighbor % width, neighbor / width);
+                if new_distance < distances[neighbor] {
+                    distances[neighbor] = new_distance;
+                    heap.push(Reverse((new_distance, neighbor)));
+                }
+            }
+        }
+        distances[end]
+    }
+}
+
+impl Solution for Grid {
+    type Parsed = Cave;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Cave> {
+        Cave::new(input)
+    }
+
+    fn part1(&self, cave: &Cave, params: &Params) -> Result<Answer> {
+        let tiles = params.int_in("tiles", 1) as usize;
+        Ok(cave.lowest_risk(tiles).into())
+    }
+
+    fn part2(&self, cave: &Cave, params: &Params) -> Result<Answer> {
+        let tiles = params.int_in("tiles", 2) as usize;
+        Ok(cave.lowest_risk(tiles).into())
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+}
@@ -0,0 +1,31 @@
+use crate::answer::Answer;
+use crate::computer::{Computer, Packet};
+use crate::error::Result;
+use crate::params::Params;
+use crate::Solution;
+
+pub struct Code;
+
+crate::register!(2021, 16);
+
+impl Solution for Code {
+    type Parsed = Packet;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Packet> {
+        let mut compy = Computer::load_input(input)?;
+        compy.parse_packet()
+    }
+
+    fn part1(&self, packet: &Packet, _params: &Params) -> Result<Answer> {
+        let version_numbers = packet.version_numbers();
+        let answer = version_numbers.iter().sum::<i64>();
+
+        Ok(answer.into())
+    }
+
+    fn part2(&self, packet: &Packet, _params: &Params) -> Result<Answer> {
+        let answer = packet.evaluate();
+
+        Ok(answer.into())
+    }
+}
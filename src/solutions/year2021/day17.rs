@@ -1,34 +1,40 @@
 use std::ops::Neg;
 
-use nom::{bytes::complete::tag, character::complete::digit1, combinator::opt, IResult};
-
-use crate::AoCDay;
+use nom::{
+    bytes::complete::tag,
+    character::complete::digit1,
+    combinator::{map_res, opt},
+    IResult,
+};
+
+use crate::answer::Answer;
+use crate::error::{LineContext, Result};
+use crate::params::{Param, Params};
+use crate::Solution;
 
 type Coord = (i32, i32);
 
+fn parse_num(input: &str) -> IResult<&str, i32> {
+    map_res(digit1, |n: &str| n.parse::<i32>())(input)
+}
+
 fn parse_target(input: &str) -> IResult<&str, (Coord, Coord)> {
     let (input, _) = tag("target area: x=")(input)?;
     let (input, x1_neg) = opt(tag("-"))(input)?;
-    let (input, x1) = digit1(input)?;
+    let (input, x1) = parse_num(input)?;
     let (input, _) = tag("..")(input)?;
     let (input, x2_neg) = opt(tag("-"))(input)?;
-    let (input, x2) = digit1(input)?;
+    let (input, x2) = parse_num(input)?;
     let (input, _) = tag(", y=")(input)?;
     let (input, y1_neg) = opt(tag("-"))(input)?;
-    let (input, y1) = digit1(input)?;
+    let (input, y1) = parse_num(input)?;
     let (input, _) = tag("..")(input)?;
     let (input, y2_neg) = opt(tag("-"))(input)?;
-    let (input, y2) = digit1(input)?;
-
-    let x1 = x1.trim().parse::<i32>().unwrap();
-    let x2 = x2.trim().parse::<i32>().unwrap();
+    let (input, y2) = parse_num(input)?;
 
     let x1 = if x1_neg.is_some() { x1.neg() } else { x1 };
     let x2 = if x2_neg.is_some() { x2.neg() } else { x2 };
 
-    let y1 = y1.trim().parse::<i32>().unwrap();
-    let y2 = y2.trim().parse::<i32>().unwrap();
-
     let y1 = if y1_neg.is_some() { y1.neg() } else { y1 };
     let y2 = if y2_neg.is_some() { y2.neg() } else { y2 };
 
@@ -37,13 +43,32 @@ fn parse_target(input: &str) -> IResult<&str, (Coord, Coord)> {
 
 pub struct Code;
 
-impl AoCDay for Code {
-    fn part1(&self, input: &str, _extra_args: &[String]) -> String {
-        let (_, ((x1, x2), (y1, y2))) = parse_target(input).unwrap();
+crate::register!(2021, 17);
+
+const PARAMS: &[Param] = &[
+    Param::int("max_vx", "Largest horizontal velocity to try", 200).range(0, 10_000),
+    Param::int(
+        "max_vy",
+        "Largest vertical velocity to try, either way",
+        200,
+    )
+    .range(0, 10_000),
+];
+
+impl Solution for Code {
+    /// The x and y ranges of the target area
+    type Parsed = (Coord, Coord);
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<(Coord, Coord)> {
+        let line = input.lines().next().unwrap_or_default();
+        let (_, target) = parse_target(line).at_line(1, line)?;
+        Ok(target)
+    }
 
+    fn part1(&self, &((x1, x2), (y1, y2)): &(Coord, Coord), params: &Params) -> Result<Answer> {
         let mut max_height = 0;
-        for x_vel in 1..=100 {
-            for y_vel in 1..=200 {
+        for x_vel in 1..=params.int("max_vx") as i32 {
+            for y_vel in 1..=params.int("max_vy") as i32 {
                 let mut x_vel = x_vel;
                 let mut y_vel = y_vel;
                 let mut pos = (0, 0);
@@ -53,7 +78,7 @@ impl AoCDay for Code {
                     pos.1 += y_vel;
                     x_vel = x_vel
                         - match x_vel {
-                            n if n == 0 => 0,
+                            0 => 0,
                             n if n < 0 => -1,
                             n if n > 0 => 1,
                             _ => unreachable!(),
@@ -69,16 +94,14 @@ impl AoCDay for Code {
         }
 
         let answer = max_height;
-        debug_assert_eq!(answer, 19503);
-        format!("{}", answer) // 19503/~1500μs
+        Ok(answer.into())
     }
 
-    fn part2(&self, input: &str, _extra_args: &[String]) -> String {
-        let (_, ((x1, x2), (y1, y2))) = parse_target(input).unwrap();
-
+    fn part2(&self, &((x1, x2), (y1, y2)): &(Coord, Coord), params: &Params) -> Result<Answer> {
         let mut number_of_hits = 0;
-        for x_vel in 1..=200 {
-            for y_vel in -200..=200 {
+        let max_vy = params.int("max_vy") as i32;
+        for x_vel in 1..=params.int("max_vx") as i32 {
+            for y_vel in -max_vy..=max_vy {
                 let mut x_vel = x_vel;
                 let mut y_vel = y_vel;
                 let mut pos = (0, 0);
@@ -88,7 +111,7 @@ impl AoCDay for Code {
                     pos.1 += y_vel;
                     x_vel = x_vel
                         - match x_vel {
-                            n if n == 0 => 0,
+                            0 => 0,
                             n if n < 0 => -1,
                             n if n > 0 => 1,
                             _ => unreachable!(),
@@ -104,7 +127,10 @@ impl AoCDay for Code {
         }
 
         let answer = number_of_hits;
-        debug_assert_eq!(answer, 5200);
-        format!("{}", answer) // 5200/~1500μs
+        Ok(answer.into())
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
     }
 }
@@ -11,17 +11,22 @@ use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::digit1,
+    combinator::map_res,
     sequence::{delimited, separated_pair},
     IResult,
 };
 
-use crate::AoCDay;
+use crate::answer::Answer;
+use crate::error::{Error, LineContext, Result};
+use crate::params::Params;
+use crate::Solution;
 
 pub struct Code;
 
+crate::register!(2021, 18);
+
 fn parse_number(input: &str) -> IResult<&str, SnailFish> {
-    let (input, num) = digit1(input)?;
-    let num = num.trim().parse::<i32>().unwrap();
+    let (input, num) = map_res(digit1, |n: &str| n.parse::<i32>())(input)?;
     Ok((input, SnailFish::Number(num)))
 }
 
@@ -44,7 +49,7 @@ fn parse(input: &str) -> IResult<&str, SnailFish> {
 }
 
 #[derive(Debug, Clone)]
-enum SnailFish {
+pub enum SnailFish {
     Number(i32),
     Pair(Box<SnailFish>, Box<SnailFish>),
 }
@@ -82,8 +87,8 @@ enum Side {
 }
 
 impl SnailFish {
-    fn from_line(line: &str) -> Self {
-        parse(line).unwrap().1
+    fn from_line(line: &str) -> std::result::Result<Self, nom::Err<nom::error::Error<&str>>> {
+        Ok(parse(line)?.1)
     }
     fn magnitude(&self) -> i32 {
         match self {
@@ -220,32 +225,39 @@ impl Display for SnailFish {
     }
 }
 
-impl AoCDay for Code {
-    fn part1(&self, input: &str, _extra_args: &[String]) -> String {
-        let answer = input
+impl Solution for Code {
+    type Parsed = Vec<SnailFish>;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Vec<SnailFish>> {
+        input
             .lines()
-            .map(SnailFish::from_line)
-            .sum::<SnailFish>()
-            .magnitude();
+            .enumerate()
+            .map(|(index, line)| SnailFish::from_line(line).at_line(index + 1, line))
+            .collect()
+    }
+
+    fn part1(&self, numbers: &Vec<SnailFish>, _params: &Params) -> Result<Answer> {
+        if numbers.is_empty() {
+            return Err(Error::no_solution("there are no numbers to add"));
+        }
+        let answer = numbers.iter().cloned().sum::<SnailFish>().magnitude();
 
-        debug_assert_eq!(answer, 3699);
-        format!("{}", answer) // 3699/3500μs
+        Ok(answer.into())
     }
 
-    fn part2(&self, input: &str, _extra_args: &[String]) -> String {    
-        let inputs = input
-            .lines()
-            .map(SnailFish::from_line)
-            .combinations(2)
-            .collect::<Vec<_>>();
-
-        let answer = inputs.par_iter().map(|combos| {
-            let mag_one = (combos[0].clone() + combos[1].clone()).magnitude();
-            let mag_two = (combos[1].clone() + combos[0].clone()).magnitude();
-            mag_one.max(mag_two)
-        }).max().unwrap();
-
-        debug_assert_eq!(answer, 4735);
-        format!("{}", answer) //4735/26896μs
+    fn part2(&self, numbers: &Vec<SnailFish>, _params: &Params) -> Result<Answer> {
+        let inputs = numbers.iter().combinations(2).collect::<Vec<_>>();
+
+        let answer = inputs
+            .par_iter()
+            .map(|combos| {
+                let mag_one = (combos[0].clone() + combos[1].clone()).magnitude();
+                let mag_two = (combos[1].clone() + combos[0].clone()).magnitude();
+                mag_one.max(mag_two)
+            })
+            .max()
+            .ok_or_else(|| Error::no_solution("there are fewer than two numbers"))?;
+
+        Ok(answer.into())
     }
 }
@@ -0,0 +1,314 @@
+use std::ops::Neg;
+
+use hashbrown::{HashMap, HashSet};
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    character::complete::digit1,
+    combinator::{map_res, opt},
+    IResult,
+};
+use std::collections::BTreeMap;
+
+use crate::answer::Answer;
+use crate::error::{Error, LineContext, Result};
+use crate::params::Params;
+use crate::Solution;
+
+pub struct Code;
+
+crate::register!(2021, 19);
+
+type Point = (i32, i32, i32);
+
+/// A rotation as the matrix it multiplies points by
+type Rotation = [[i32; 3]; 3];
+
+/// Scanners overlap when they see at least this many of the same beacons
+const OVERLAP: usize = 12;
+
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    header: i32,
+    points: Vec<Point>,
+    // Keyed on squared distance, which stays exact. Different pairs can be the same distance
+    // apart, so each distance has all of them
+    distances: BTreeMap<i32, Vec<(Point, Point)>>,
+}
+
+fn parse_num(input: &str) -> IResult<&str, i32> {
+    map_res(digit1, |n: &str| n.parse::<i32>())(input)
+}
+
+fn parse_header(input: &str) -> IResult<&str, i32> {
+    let (input, _) = tag("--- scanner ")(input)?;
+    let (input, header) = parse_num(input)?;
+    Ok((input, header))
+}
+
+fn convert_num(num: i32, neg: Option<&str>) -> i32 {
+    if neg.is_some() {
+        num.neg()
+    } else {
+        num
+    }
+}
+
+fn parse_coords(input: &str) -> IResult<&str, (i32, i32, i32)> {
+    let (input, x_neg) = opt(tag("-"))(input)?;
+    let (input, x) = parse_num(input)?;
+    let (input, _) = tag(",")(input)?;
+
+    let (input, y_neg) = opt(tag("-"))(input)?;
+    let (input, y) = parse_num(input)?;
+    let (input, _) = tag(",")(input)?;
+
+    let (input, z_neg) = opt(tag("-"))(input)?;
+    let (input, z) = parse_num(input)?;
+
+    Ok((
+        input,
+        (
+            convert_num(x, x_neg),
+            convert_num(y, y_neg),
+            convert_num(z, z_neg),
+        ),
+    ))
+}
+
+fn sq(n: i32) -> i32 {
+    n * n
+}
+
+fn parse_scanners(input: &str) -> Result<Vec<Scanner>> {
+    let mut new_scanner = true;
+    let mut scanners = vec![];
+    let mut current_scanner = Scanner {
+        header: 0,
+        points: vec![],
+        distances: BTreeMap::new(),
+    };
+    // The last scanner isn't followed by a blank line
+    for (index, line) in input.lines().chain([""]).enumerate() {
+        if line.is_empty() {
+            new_scanner = true;
+            let mut distances = BTreeMap::new();
+            for combos in current_scanner.points.iter().combinations(2) {
+                let p1 @ (x1, y1, z1) = combos[0];
+                let p2 @ (x2, y2, z2) = combos[1];
+                let distance = sq(x2 - x1) + sq(y2 - y1) + sq(z2 - z1);
+                distances
+                    .entry(distance)
+                    .or_insert_with(Vec::new)
+                    .push((*p1, *p2));
+            }
+            current_scanner.distances = distances;
+            if !current_scanner.points.is_empty() {
+                scanners.push(current_scanner);
+            }
+            current_scanner = Scanner {
+                header: 0,
+                points: vec![],
+                distances: BTreeMap::new(),
+            };
+            continue;
+        }
+        if new_scanner {
+            let (_, header) = parse_header(line).at_line(index + 1, line)?;
+            current_scanner.header = header;
+            new_scanner = false;
+        } else {
+            let (_, (x, y, z)) = parse_coords(line).at_line(index + 1, line)?;
+            current_scanner.points.push((x, y, z));
+        }
+    }
+    Ok(scanners)
+}
+
+/// The 24 ways a scanner can be facing: every permutation of the axes with every choice of
+/// signs, except the ones that mirror instead of rotating
+fn rotations() -> Vec<Rotation> {
+    let mut rotations = vec![];
+    for axes in (0..3).permutations(3) {
+        for signs in 0..8 {
+            let mut rotation = [[0; 3]; 3];
+            for (row, &axis) in axes.iter().enumerate() {
+                rotation[row][axis] = if signs & (1 << row) == 0 { 1 } else { -1 };
+            }
+            if determinant(&rotation) == 1 {
+                rotations.push(rotation);
+            }
+        }
+    }
+    rotations
+}
+
+fn determinant(m: &Rotation) -> i32 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+fn rotate(m: &Rotation, (x, y, z): Point) -> Point {
+    (
+        m[0][0] * x + m[0][1] * y + m[0][2] * z,
+        m[1][0] * x + m[1][1] * y + m[1][2] * z,
+        m[2][0] * x + m[2][1] * y + m[2][2] * z,
+    )
+}
+
+fn add((x1, y1, z1): Point, (x2, y2, z2): Point) -> Point {
+    (x1 + x2, y1 + y2, z1 + z2)
+}
+
+fn sub((x1, y1, z1): Point, (x2, y2, z2): Point) -> Point {
+    (x1 - x2, y1 - y2, z1 - z2)
+}
+
+fn manhattan(p1: Point, p2: Point) -> i32 {
+    let (x, y, z) = sub(p1, p2);
+    x.abs() + y.abs() + z.abs()
+}
+
+/// Every beacon, and where each scanner is, relative to scanner 0
+pub struct Aligned {
+    beacons: HashSet<Point>,
+    positions: Vec<Point>,
+}
+
+/// How to turn and move `other` so that its beacons line up with `known`'s, which are
+/// already relative to scanner 0. Pairs of beacons the same distance apart in both scanners
+/// are likely to be the same pairs, so each of them votes for the rotation and offset that
+/// would line it up, and the most popular one is checked against every beacon
+fn align(
+    rotations: &[Rotation],
+    known: &Scanner,
+    known_beacons: &HashSet<Point>,
+    other: &Scanner,
+) -> Option<(Rotation, Point)> {
+    let shared = other.distances.iter().filter_map(|(distance, pairs)| {
+        let known_pairs = known.distances.get(distance)?;
+        Some(known_pairs.iter().cartesian_product(pairs))
+    });
+    let mut votes = HashMap::new();
+    for ((p1, p2), (q1, q2)) in shared.flatten() {
+        for (index, rotation) in rotations.iter().enumerate() {
+            let (r1, r2) = (rotate(rotation, *q1), rotate(rotation, *q2));
+            // Which end of the pair is which isn't known
+            if sub(r2, r1) == sub(*p2, *p1) {
+                *votes.entry((index, sub(*p1, r1))).or_insert(0) += 1;
+            } else if sub(r2, r1) == sub(*p1, *p2) {
+                *votes.entry((index, sub(*p2, r1))).or_insert(0) += 1;
+            }
+        }
+    }
+    let (&(index, offset), _) = votes.iter().max_by_key(|(_, &count)| count)?;
+    let rotation = rotations[index];
+    let matching = other
+        .points
+        .iter()
+        .filter(|&&point| known_beacons.contains(&add(rotate(&rotation, point), offset)))
+        .count();
+    (matching >= OVERLAP).then_some((rotation, offset))
+}
+
+/// Lines every scanner up with scanner 0, going out from the ones already lined up
+fn align_all(scanners: &[Scanner]) -> Result<Aligned> {
+    let rotations = rotations();
+    let first = scanners
+        .first()
+        .ok_or_else(|| Error::no_solution("there are no scanners"))?;
+    // Scanners relative to scanner 0, along with their beacons as a set
+    let mut aligned: Vec<Option<(Scanner, HashSet<Point>)>> =
+        scanners.iter().map(|_| None).collect();
+    let mut positions = vec![(0, 0, 0); scanners.len()];
+    aligned[0] = Some((first.clone(), first.points.iter().copied().collect()));
+    let mut frontier = vec![0];
+    while let Some(known) = frontier.pop() {
+        for (index, scanner) in scanners.iter().enumerate() {
+            if aligned[index].is_some() {
+                continue;
+            }
+            let (known_scanner, known_beacons) = aligned[known].as_ref().unwrap();
+            if let Some((rotation, offset)) =
+                align(&rotations, known_scanner, known_beacons, scanner)
+            {
+                let moved = scanner.moved(&rotation, offset);
+                let beacons = moved.points.iter().copied().collect();
+                aligned[index] = Some((moved, beacons));
+                positions[index] = offset;
+                frontier.push(index);
+            }
+        }
+    }
+    if let Some(index) = aligned.iter().position(Option::is_none) {
+        return Err(Error::no_solution(format!(
+            "scanner {} doesn't overlap any other",
+            scanners[index].header
+        )));
+    }
+    let beacons = aligned
+        .into_iter()
+        .flatten()
+        .flat_map(|(_, beacons)| beacons)
+        .collect();
+    Ok(Aligned { beacons, positions })
+}
+
+impl Scanner {
+    /// Turned by `rotation` then moved by `offset`. Distances stay the same
+    fn moved(&self, rotation: &Rotation, offset: Point) -> Scanner {
+        let moved = |point| add(rotate(rotation, point), offset);
+        Scanner {
+            header: self.header,
+            points: self.points.iter().map(|&point| moved(point)).collect(),
+            distances: self
+                .distances
+                .iter()
+                .map(|(&distance, pairs)| {
+                    let pairs = pairs
+                        .iter()
+                        .map(|&(p1, p2)| (moved(p1), moved(p2)))
+                        .collect();
+                    (distance, pairs)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Aligned {
+    fn largest_distance(&self) -> i32 {
+        self.positions
+            .iter()
+            .tuple_combinations()
+            .map(|(&p1, &p2)| manhattan(p1, p2))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl Solution for Code {
+    type Parsed = Vec<Scanner>;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Vec<Scanner>> {
+        parse_scanners(input)
+    }
+
+    fn part1(&self, scanners: &Vec<Scanner>, _params: &Params) -> Result<Answer> {
+        Ok(align_all(scanners)?.beacons.len().into())
+    }
+
+    fn part2(&self, scanners: &Vec<Scanner>, _params: &Params) -> Result<Answer> {
+        Ok(align_all(scanners)?.largest_distance().into())
+    }
+
+    /// Aligning is most of the work, and both parts need it
+    fn both(&self, scanners: &Vec<Scanner>, _params: &Params) -> Result<(Answer, Answer)> {
+        let aligned = align_all(scanners)?;
+        Ok((
+            aligned.beacons.len().into(),
+            aligned.largest_distance().into(),
+        ))
+    }
+}
@@ -1,21 +1,27 @@
-use crate::AoCDay;
+use crate::answer::Answer;
+use crate::error::{LineContext, Result};
+use crate::params::Params;
+use crate::Solution;
 
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{digit1, multispace1},
+    combinator::map_res,
     IResult,
 };
 
 pub struct Code;
 
+crate::register!(2021, 2);
+
 struct Position {
     pub horizontal: i32,
     pub depth: i32,
     pub aim: i32,
 }
 
-enum Command {
+pub enum Command {
     Forward(i32),
     Down(i32),
     Up(i32),
@@ -24,8 +30,7 @@ enum Command {
 fn parse_command(input: &str) -> IResult<&str, Command> {
     let (input, dir) = alt((tag("forward"), tag("down"), tag("up")))(input)?;
     let (input, _) = multispace1(input)?;
-    let (input, magnitude) = digit1(input)?;
-    let num = magnitude.trim().parse::<i32>().unwrap();
+    let (input, num) = map_res(digit1, |n: &str| n.parse::<i32>())(input)?;
     match dir {
         "forward" => Ok((input, Command::Forward(num))),
         "down" => Ok((input, Command::Down(num))),
@@ -34,38 +39,47 @@ fn parse_command(input: &str) -> IResult<&str, Command> {
     }
 }
 
-impl AoCDay for Code {
-    fn part1(&self, input: &str, _extra_args: &[String]) -> String {
+impl Solution for Code {
+    type Parsed = Vec<Command>;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Vec<Command>> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                let (_, command) = parse_command(line).at_line(index + 1, line)?;
+                Ok(command)
+            })
+            .collect()
+    }
+
+    fn part1(&self, commands: &Vec<Command>, _params: &Params) -> Result<Answer> {
         let mut pos = Position {
             horizontal: 0,
             depth: 0,
             aim: 0,
         };
 
-        for line in input.lines() {
-            let (_, command) = parse_command(line).unwrap();
-
-            match command {
+        for command in commands {
+            match *command {
                 Command::Forward(n) => pos.horizontal += n,
                 Command::Down(n) => pos.depth += n,
                 Command::Up(n) => pos.depth -= n,
             }
         }
         let answer = pos.horizontal * pos.depth;
-        debug_assert_eq!(answer, 2187380);
-        format!("{}", pos.horizontal * pos.depth) // 2187380/~70μs
+        Ok(answer.into())
     }
 
-    fn part2(&self, input: &str, _extra_args: &[String]) -> String {
+    fn part2(&self, commands: &Vec<Command>, _params: &Params) -> Result<Answer> {
         let mut pos = Position {
             horizontal: 0,
             depth: 0,
             aim: 0,
         };
 
-        for line in input.lines() {
-            let (_, command) = parse_command(line).unwrap();
-            match command {
+        for command in commands {
+            match *command {
                 Command::Forward(n) => {
                     pos.horizontal += n;
                     pos.depth += pos.aim * n
@@ -75,7 +89,6 @@ impl AoCDay for Code {
             }
         }
         let answer = pos.horizontal * pos.depth;
-        debug_assert_eq!(answer, 2086357770);
-        format!("{}", answer) // 2086357770/~70μs
+        Ok(answer.into())
     }
 }
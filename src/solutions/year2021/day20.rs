@@ -0,0 +1,157 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::params::{Param, Params};
+use crate::Solution;
+
+pub struct Code;
+
+crate::register!(2021, 20);
+
+/// The image grows by a pixel on every side with each step
+const PARAMS: &[Param] = &[Param::int("steps", "Times the image is enhanced", 2)
+    .part2(50)
+    .range(0, 1_000)];
+
+fn pixel(c: char) -> Option<bool> {
+    match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    }
+}
+
+/// The pixels that were scanned, surrounded by infinitely many more that are all the same
+#[derive(Debug, Clone)]
+pub struct Image {
+    pixels: Vec<bool>,
+    width: usize,
+    height: usize,
+    /// Every pixel outside of `pixels`
+    background: bool,
+}
+
+impl Image {
+    /// Outside of the image is the background
+    #[inline(always)]
+    fn get(&self, x: i64, y: i64) -> bool {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return self.background;
+        }
+        self.pixels[x as usize + y as usize * self.width]
+    }
+
+    /// Each pixel looks at the 3x3 square around it, which can reach one pixel past the
+    /// image, so the image grows by one on each side. The background all looks at the
+    /// same square of background pixels, so it stays the same everywhere, but it can
+    /// change: when the first entry of the algorithm is lit, the dark background lights up
+    fn enhance(&self, algorithm: &[bool; 512]) -> Image {
+        let (width, height) = (self.width + 2, self.height + 2);
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height as i64 {
+            for x in 0..width as i64 {
+                let mut index = 0;
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        index = index << 1 | self.get(x - 1 + dx, y - 1 + dy) as usize;
+                    }
+                }
+                pixels.push(algorithm[index]);
+            }
+        }
+        let background = match self.background {
+            true => algorithm[511],
+            false => algorithm[0],
+        };
+        Image {
+            pixels,
+            width,
+            height,
+            background,
+        }
+    }
+
+    fn lit(&self) -> Result<usize> {
+        if self.background {
+            return Err(Error::no_solution("infinitely many pixels are lit"));
+        }
+        Ok(self.pixels.iter().filter(|&&pixel| pixel).count())
+    }
+}
+
+fn parse_input(input: &str) -> Result<([bool; 512], Image)> {
+    let mut lines = input.lines().enumerate();
+    let (_, first) = lines
+        .next()
+        .ok_or_else(|| Error::no_solution("the input is empty"))?;
+    if first.chars().count() != 512 {
+        return Err(Error::parse(1, first, "expected 512 pixels"));
+    }
+    let mut algorithm = [false; 512];
+    for (entry, c) in algorithm.iter_mut().zip(first.chars()) {
+        *entry =
+            pixel(c).ok_or_else(|| Error::parse(1, first, format!("{:?} is not a pixel", c)))?;
+    }
+    match lines.next() {
+        Some((_, "")) => {}
+        Some((index, line)) => return Err(Error::parse(index + 1, line, "expected a blank line")),
+        None => return Err(Error::no_solution("there is no image")),
+    }
+
+    let mut pixels = vec![];
+    let mut width = 0;
+    let mut height = 0;
+    for (index, line) in lines {
+        if height > 0 && line.chars().count() != width {
+            return Err(Error::parse(
+                index + 1,
+                line,
+                format!("expected {} pixels", width),
+            ));
+        }
+        for c in line.chars() {
+            let pixel = pixel(c)
+                .ok_or_else(|| Error::parse(index + 1, line, format!("{:?} is not a pixel", c)))?;
+            pixels.push(pixel);
+        }
+        width = line.chars().count();
+        height += 1;
+    }
+    if pixels.is_empty() {
+        return Err(Error::no_solution("there is no image"));
+    }
+    let image = Image {
+        pixels,
+        width,
+        height,
+        background: false,
+    };
+    Ok((algorithm, image))
+}
+
+fn lit_after(algorithm: &[bool; 512], image: &Image, steps: i64) -> Result<Answer> {
+    let mut image = image.clone();
+    for _ in 0..steps {
+        image = image.enhance(algorithm);
+    }
+    Ok(image.lit()?.into())
+}
+
+impl Solution for Code {
+    type Parsed = ([bool; 512], Image);
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<([bool; 512], Image)> {
+        parse_input(input)
+    }
+
+    fn part1(&self, (algorithm, image): &([bool; 512], Image), params: &Params) -> Result<Answer> {
+        lit_after(algorithm, image, params.int_in("steps", 1))
+    }
+
+    fn part2(&self, (algorithm, image): &([bool; 512], Image), params: &Params) -> Result<Answer> {
+        lit_after(algorithm, image, params.int_in("steps", 2))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+}
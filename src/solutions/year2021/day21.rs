@@ -0,0 +1,180 @@
+use hashbrown::HashMap;
+
+use crate::answer::Answer;
+use crate::error::{Error, LineContext, Result};
+use crate::params::{Param, Params};
+use crate::Solution;
+
+pub struct Code;
+
+crate::register!(2021, 21);
+
+const PARAMS: &[Param] = &[
+    Param::int("board", "Spaces around the board", 10).range(1, 1_000),
+    Param::int("score", "Score that wins", 1_000)
+        .part2(21)
+        .range(1, 1_000),
+    Param::int("sides", "Sides of the die", 100)
+        .part2(3)
+        .range(1, 1_000),
+];
+
+/// Each turn is three rolls of the die
+const ROLLS: usize = 3;
+
+/// The rules, from the parameters
+struct Game {
+    board: u32,
+    score: u32,
+    sides: u32,
+}
+
+impl Game {
+    fn new(params: &Params, part: u8) -> Self {
+        Game {
+            board: params.int("board") as u32,
+            score: params.int_in("score", part) as u32,
+            sides: params.int_in("sides", part) as u32,
+        }
+    }
+
+    /// Spaces are numbered from 1
+    fn advance(&self, position: u32, by: u32) -> u32 {
+        (position - 1 + by) % self.board + 1
+    }
+
+    fn check(&self, positions: [u32; 2]) -> Result<()> {
+        match positions.iter().find(|&&position| position > self.board) {
+            Some(position) => Err(Error::no_solution(format!(
+                "position {} is off a board of {}",
+                position, self.board
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// The losing score times how many times the die was rolled, with a die that rolls
+    /// 1, 2, 3... up to its number of sides and then starts over
+    fn deterministic(&self, mut positions: [u32; 2]) -> u64 {
+        let mut scores = [0; 2];
+        let mut rolls = 0u64;
+        let mut die = (1..=self.sides).cycle();
+        for player in (0..2).cycle() {
+            let moved = die.by_ref().take(ROLLS).sum::<u32>();
+            rolls += ROLLS as u64;
+            positions[player] = self.advance(positions[player], moved);
+            scores[player] += positions[player];
+            if scores[player] >= self.score {
+                break;
+            }
+        }
+        scores.iter().min().copied().unwrap_or_default() as u64 * rolls
+    }
+
+    /// How many ways three rolls add up to each total
+    fn totals(&self) -> Vec<(u32, u128)> {
+        let mut totals = HashMap::new();
+        totals.insert(0, 1u128);
+        for _ in 0..ROLLS {
+            let mut next = HashMap::new();
+            for (total, ways) in totals {
+                for side in 1..=self.sides {
+                    *next.entry(total + side).or_insert(0) += ways;
+                }
+            }
+            totals = next;
+        }
+        let mut totals = totals.into_iter().collect::<Vec<_>>();
+        totals.sort_unstable();
+        totals
+    }
+}
+
+/// Whose turn it is, and where both players are
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct State {
+    positions: [u32; 2],
+    scores: [u32; 2],
+    turn: usize,
+}
+
+/// In how many universes each player wins from `state`, unless there are too many to count.
+/// Many games pass through the same states, so each state is only counted once
+fn wins(
+    game: &Game,
+    totals: &[(u32, u128)],
+    state: State,
+    memo: &mut HashMap<State, [u128; 2]>,
+) -> Option<[u128; 2]> {
+    if let Some(&wins) = memo.get(&state) {
+        return Some(wins);
+    }
+    let player = state.turn;
+    let mut result = [0u128; 2];
+    for &(total, ways) in totals {
+        let mut next = state;
+        next.positions[player] = game.advance(state.positions[player], total);
+        next.scores[player] += next.positions[player];
+        if next.scores[player] >= game.score {
+            result[player] = result[player].checked_add(ways)?;
+            continue;
+        }
+        next.turn = 1 - player;
+        let later = wins(game, totals, next, memo)?;
+        for (result, later) in result.iter_mut().zip(later) {
+            *result = result.checked_add(later.checked_mul(ways)?)?;
+        }
+    }
+    memo.insert(state, result);
+    Some(result)
+}
+
+fn parse_positions(input: &str) -> Result<[u32; 2]> {
+    let mut positions = [0; 2];
+    let mut lines = input.lines().enumerate();
+    for (player, position) in positions.iter_mut().enumerate() {
+        let (index, line) = lines
+            .next()
+            .ok_or_else(|| Error::no_solution("there need to be two players"))?;
+        let prefix = format!("Player {} starting position: ", player + 1);
+        let number = line.strip_prefix(&prefix).ok_or_else(|| {
+            Error::parse(index + 1, line, format!("expected {:?}", prefix.trim_end()))
+        })?;
+        *position = number.parse().at_line(index + 1, line)?;
+        if *position == 0 {
+            return Err(Error::parse(index + 1, line, "spaces start at 1"));
+        }
+    }
+    Ok(positions)
+}
+
+impl Solution for Code {
+    type Parsed = [u32; 2];
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<[u32; 2]> {
+        parse_positions(input)
+    }
+
+    fn part1(&self, positions: &[u32; 2], params: &Params) -> Result<Answer> {
+        let game = Game::new(params, 1);
+        game.check(*positions)?;
+        Ok(game.deterministic(*positions).into())
+    }
+
+    fn part2(&self, positions: &[u32; 2], params: &Params) -> Result<Answer> {
+        let game = Game::new(params, 2);
+        game.check(*positions)?;
+        let start = State {
+            positions: *positions,
+            scores: [0; 2],
+            turn: 0,
+        };
+        let wins = wins(&game, &game.totals(), start, &mut HashMap::new())
+            .ok_or_else(|| Error::no_solution("there are too many universes to count"))?;
+        Ok(wins.iter().max().copied().unwrap_or_default().into())
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+}
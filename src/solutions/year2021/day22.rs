@@ -0,0 +1,212 @@
+use std::fmt::{self, Display};
+
+use crate::answer::Answer;
+use crate::error::{Error, LineContext, Result};
+use crate::params::Params;
+use crate::Solution;
+
+pub struct Code;
+
+crate::register!(2021, 22);
+
+/// Part 1 only looks at the cubes from -50 to 50 on every axis
+const INITIALIZATION: Cuboid = Cuboid {
+    min: [-50; 3],
+    max: [50; 3],
+};
+
+/// Every cube from `min` to `max` on each axis, both included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cuboid {
+    min: [i64; 3],
+    max: [i64; 3],
+}
+
+impl Cuboid {
+    /// The lowest corner
+    pub fn min(&self) -> [i64; 3] {
+        self.min
+    }
+
+    /// The highest corner, which is part of the cuboid
+    pub fn max(&self) -> [i64; 3] {
+        self.max
+    }
+
+    /// How many cubes there are
+    pub fn volume(&self) -> u64 {
+        (0..3)
+            .map(|axis| (self.max[axis] - self.min[axis] + 1) as u64)
+            .product()
+    }
+
+    /// The cubes that are in both, if there are any
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let mut intersection = *self;
+        for axis in 0..3 {
+            intersection.min[axis] = self.min[axis].max(other.min[axis]);
+            intersection.max[axis] = self.max[axis].min(other.max[axis]);
+            if intersection.min[axis] > intersection.max[axis] {
+                return None;
+            }
+        }
+        Some(intersection)
+    }
+
+    /// What's left of this cuboid once `other` is taken out of it, as up to six cuboids
+    /// that don't overlap: slabs are cut off on either side of `other`, one axis at a time,
+    /// and what remains in the middle is the part `other` covers
+    fn subtract(&self, other: &Cuboid) -> Vec<Cuboid> {
+        let overlap = match self.intersection(other) {
+            Some(overlap) => overlap,
+            None => return vec![*self],
+        };
+        let mut pieces = vec![];
+        let mut rest = *self;
+        for axis in 0..3 {
+            if rest.min[axis] < overlap.min[axis] {
+                let mut below = rest;
+                below.max[axis] = overlap.min[axis] - 1;
+                pieces.push(below);
+                rest.min[axis] = overlap.min[axis];
+            }
+            if rest.max[axis] > overlap.max[axis] {
+                let mut above = rest;
+                above.min[axis] = overlap.max[axis] + 1;
+                pieces.push(above);
+                rest.max[axis] = overlap.max[axis];
+            }
+        }
+        pieces
+    }
+}
+
+impl Display for Cuboid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "x={}..{},y={}..{},z={}..{}",
+            self.min[0], self.max[0], self.min[1], self.max[1], self.min[2], self.max[2]
+        )
+    }
+}
+
+/// One line of the reboot steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    on: bool,
+    cuboid: Cuboid,
+}
+
+/// The cubes that are on, as cuboids that don't overlap
+#[derive(Debug, Clone, Default)]
+pub struct Reactor {
+    cuboids: Vec<Cuboid>,
+}
+
+impl Reactor {
+    /// Carries out `steps`, ignoring every cube outside of `region` if there is one
+    pub fn reboot(steps: &[Step], region: Option<&Cuboid>) -> Self {
+        let mut reactor = Reactor::default();
+        for step in steps {
+            let cuboid = match region {
+                Some(region) => match step.cuboid.intersection(region) {
+                    Some(cuboid) => cuboid,
+                    None => continue,
+                },
+                None => step.cuboid,
+            };
+            reactor.switch(&cuboid, step.on);
+        }
+        reactor
+    }
+
+    /// Whatever was on in `cuboid` is cut out first, so that the cuboids never overlap
+    fn switch(&mut self, cuboid: &Cuboid, on: bool) {
+        self.cuboids = self
+            .cuboids
+            .iter()
+            .flat_map(|lit| lit.subtract(cuboid))
+            .collect();
+        if on {
+            self.cuboids.push(*cuboid);
+        }
+    }
+
+    /// The cuboids that are on, which don't overlap
+    pub fn cuboids(&self) -> &[Cuboid] {
+        &self.cuboids
+    }
+
+    /// How many cubes are on
+    pub fn lit(&self) -> u64 {
+        self.cuboids.iter().map(Cuboid::volume).sum()
+    }
+}
+
+/// `a..b`, in either order
+fn parse_range(range: &str) -> std::result::Result<(i64, i64), String> {
+    let (from, to) = range
+        .split_once("..")
+        .ok_or_else(|| format!("expected a range, not {:?}", range))?;
+    let from = from.parse::<i64>().map_err(|e| e.to_string())?;
+    let to = to.parse::<i64>().map_err(|e| e.to_string())?;
+    Ok((from.min(to), from.max(to)))
+}
+
+fn parse_step(line: &str) -> std::result::Result<Step, String> {
+    let (state, ranges) = line
+        .split_once(' ')
+        .ok_or_else(|| "expected on or off, then the cuboid".to_string())?;
+    let on = match state {
+        "on" => true,
+        "off" => false,
+        _ => return Err(format!("{:?} is not on or off", state)),
+    };
+    let mut cuboid = Cuboid {
+        min: [0; 3],
+        max: [0; 3],
+    };
+    let mut axes = ranges.split(',');
+    for (axis, name) in ["x=", "y=", "z="].iter().enumerate() {
+        let range = axes
+            .next()
+            .and_then(|range| range.strip_prefix(name))
+            .ok_or_else(|| format!("expected {}", name))?;
+        let (min, max) = parse_range(range)?;
+        cuboid.min[axis] = min;
+        cuboid.max[axis] = max;
+    }
+    if axes.next().is_some() {
+        return Err("expected three ranges".to_string());
+    }
+    Ok(Step { on, cuboid })
+}
+
+fn parse_steps(input: &str) -> Result<Vec<Step>> {
+    let steps = input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse_step(line).at_line(index + 1, line))
+        .collect::<Result<Vec<_>>>()?;
+    if steps.is_empty() {
+        return Err(Error::no_solution("there are no reboot steps"));
+    }
+    Ok(steps)
+}
+
+impl Solution for Code {
+    type Parsed = Vec<Step>;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Vec<Step>> {
+        parse_steps(input)
+    }
+
+    fn part1(&self, steps: &Vec<Step>, _params: &Params) -> Result<Answer> {
+        Ok(Reactor::reboot(steps, Some(&INITIALIZATION)).lit().into())
+    }
+
+    fn part2(&self, steps: &Vec<Step>, _params: &Params) -> Result<Answer> {
+        Ok(Reactor::reboot(steps, None).lit().into())
+    }
+}
@@ -0,0 +1,286 @@
+use std::fmt::{self, Display};
+
+use pathfinding::prelude::astar;
+
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::params::{Param, Params};
+use crate::Solution;
+
+pub struct Code;
+
+crate::register!(2021, 23);
+
+const PARAMS: &[Param] = &[Param::flag(
+    "moves",
+    "Show the burrow after each move of the cheapest solution, below the energy",
+)];
+
+/// Rows part 2 finds folded up in the middle of the rooms
+const UNFOLDED: [[u8; ROOMS]; 2] = [[4, 3, 2, 1], [4, 2, 1, 3]];
+
+const ROOMS: usize = 4;
+const HALLWAY: usize = 11;
+/// The deepest rooms either part has
+const MAX_DEPTH: usize = 4;
+/// Energy each kind of amphipod takes to move one step
+const ENERGY: [u32; ROOMS] = [1, 10, 100, 1000];
+
+/// The hallway space in front of `room`, where nobody can stop
+fn door(room: usize) -> usize {
+    2 + 2 * room
+}
+
+/// Amphipods are 1 to 4 for A to D, and 0 is an empty space
+fn amphipod(c: char) -> Option<u8> {
+    match c {
+        'A'..='D' => Some(c as u8 - b'A' + 1),
+        _ => None,
+    }
+}
+
+fn letter(amphipod: u8) -> char {
+    match amphipod {
+        0 => '.',
+        a => (b'A' + a - 1) as char,
+    }
+}
+
+/// Where every amphipod is. Room spaces go from the top, next to the hallway, down
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Burrow {
+    hallway: [u8; HALLWAY],
+    rooms: [[u8; MAX_DEPTH]; ROOMS],
+    depth: usize,
+}
+
+impl Burrow {
+    /// Room `room` is where amphipod `room + 1` goes
+    fn is_sorted(&self) -> bool {
+        self.rooms
+            .iter()
+            .enumerate()
+            .all(|(room, spaces)| spaces[..self.depth].iter().all(|&a| a as usize == room + 1))
+    }
+
+    /// No one in the room needs to leave it, so its own amphipods can move in
+    fn is_ready(&self, room: usize) -> bool {
+        self.rooms[room][..self.depth]
+            .iter()
+            .all(|&a| a == 0 || a as usize == room + 1)
+    }
+
+    /// Every space of the hallway strictly between `from` and `to`, and `to` itself, is free
+    fn is_clear(&self, from: usize, to: usize) -> bool {
+        let spaces = match from < to {
+            true => from + 1..=to,
+            false => to..=from - 1,
+        };
+        self.hallway[spaces].iter().all(|&a| a == 0)
+    }
+
+    /// Every burrow one move away, and the energy that move takes. Amphipods either leave
+    /// their room for a space in the hallway, or go from the hallway to the bottom of their
+    /// own room. Going straight from room to room costs the same as stopping in the hallway
+    /// on the way, which is always possible as there's a space between each pair of doors
+    fn moves(&self) -> Vec<(Burrow, u32)> {
+        let mut moves = vec![];
+        for (space, &a) in self.hallway.iter().enumerate() {
+            let room = match a {
+                0 => continue,
+                a => a as usize - 1,
+            };
+            if !self.is_ready(room) || !self.is_clear(space, door(room)) {
+                continue;
+            }
+            let depth = self.rooms[room][..self.depth]
+                .iter()
+                .rposition(|&a| a == 0)
+                .expect("a ready room with an amphipod outside has space");
+            let mut next = *self;
+            next.hallway[space] = 0;
+            next.rooms[room][depth] = a;
+            let steps = space.abs_diff(door(room)) + depth + 1;
+            moves.push((next, steps as u32 * ENERGY[room]));
+        }
+        for room in 0..ROOMS {
+            if self.is_ready(room) {
+                continue;
+            }
+            let depth = match self.rooms[room][..self.depth].iter().position(|&a| a != 0) {
+                Some(depth) => depth,
+                None => continue,
+            };
+            let a = self.rooms[room][depth];
+            let stops = (0..HALLWAY).filter(|&space| !(0..ROOMS).any(|r| door(r) == space));
+            for space in stops {
+                if !self.is_clear(door(room), space) {
+                    continue;
+                }
+                let mut next = *self;
+                next.rooms[room][depth] = 0;
+                next.hallway[space] = a;
+                let steps = depth + 1 + door(room).abs_diff(space);
+                moves.push((next, steps as u32 * ENERGY[a as usize - 1]));
+            }
+        }
+        moves
+    }
+
+    /// The least energy left to spend, ignoring everyone else in the way and how deep in
+    /// its room each amphipod ends up, so it's never more than it really takes
+    fn estimate(&self) -> u32 {
+        let mut energy = 0;
+        for (space, &a) in self.hallway.iter().enumerate() {
+            if a != 0 {
+                let room = a as usize - 1;
+                energy += (space.abs_diff(door(room)) + 1) as u32 * ENERGY[room];
+            }
+        }
+        for room in 0..ROOMS {
+            for depth in 0..self.depth {
+                let a = self.rooms[room][depth];
+                if a == 0 {
+                    continue;
+                }
+                let target = a as usize - 1;
+                // Staying only works if nobody below has to leave
+                let blocking = self.rooms[room][depth + 1..self.depth]
+                    .iter()
+                    .any(|&below| below as usize != room + 1);
+                if target == room && !blocking {
+                    continue;
+                }
+                // Out to the hallway, over to the door, and one step in. Leaving its own
+                // room means stepping aside to come back
+                let across = door(room).abs_diff(door(target)).max(2);
+                let steps = depth + 1 + across + 1;
+                energy += steps as u32 * ENERGY[target];
+            }
+        }
+        energy
+    }
+
+    /// With `UNFOLDED` below the top row of each room. Burrows that were already unfolded
+    /// stay as they are
+    fn unfolded(&self) -> Result<Burrow> {
+        match self.depth + UNFOLDED.len() {
+            MAX_DEPTH => {}
+            _ if self.depth == MAX_DEPTH => return Ok(*self),
+            _ => {
+                return Err(Error::no_solution(format!(
+                    "part 2 needs rooms {} or {} deep",
+                    MAX_DEPTH - UNFOLDED.len(),
+                    MAX_DEPTH
+                )))
+            }
+        }
+        let mut burrow = *self;
+        burrow.depth += UNFOLDED.len();
+        for room in 0..ROOMS {
+            let mut spaces = vec![self.rooms[room][0]];
+            spaces.extend(UNFOLDED.iter().map(|row| row[room]));
+            spaces.extend(&self.rooms[room][1..self.depth]);
+            burrow.rooms[room][..spaces.len()].copy_from_slice(&spaces);
+        }
+        Ok(burrow)
+    }
+}
+
+/// The burrow as the puzzle draws it
+impl Display for Burrow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "#############")?;
+        let hallway = self.hallway.iter().map(|&a| letter(a)).collect::<String>();
+        writeln!(f, "#{}#", hallway)?;
+        for depth in 0..self.depth {
+            let row = (0..ROOMS)
+                .map(|room| letter(self.rooms[room][depth]).to_string())
+                .collect::<Vec<_>>()
+                .join("#");
+            match depth {
+                0 => writeln!(f, "###{}###", row)?,
+                _ => writeln!(f, "  #{}#", row)?,
+            }
+        }
+        write!(f, "  #########")
+    }
+}
+
+fn parse_burrow(input: &str) -> Result<Burrow> {
+    let mut burrow = Burrow {
+        hallway: [0; HALLWAY],
+        rooms: [[0; MAX_DEPTH]; ROOMS],
+        depth: 0,
+    };
+    let lines = input.lines().collect::<Vec<_>>();
+    // The walls above and below aren't needed, the rooms are the lines in between
+    let rows = lines.len().saturating_sub(3);
+    if rows == 0 {
+        return Err(Error::no_solution("there are no rooms"));
+    }
+    if rows > MAX_DEPTH {
+        return Err(Error::no_solution(format!(
+            "rooms can't be more than {} deep",
+            MAX_DEPTH
+        )));
+    }
+    let hallway = lines[1];
+    if hallway != "#...........#" {
+        return Err(Error::parse(2, hallway, "expected an empty hallway"));
+    }
+    for (depth, line) in lines[2..2 + rows].iter().enumerate() {
+        let chars = line.chars().collect::<Vec<_>>();
+        for room in 0..ROOMS {
+            let c = chars.get(door(room) + 1).copied().unwrap_or(' ');
+            burrow.rooms[room][depth] = amphipod(c).ok_or_else(|| {
+                Error::parse(depth + 3, line, format!("{:?} is not an amphipod", c))
+            })?;
+        }
+    }
+    burrow.depth = rows;
+    for a in 1..=ROOMS as u8 {
+        let count = burrow.rooms.iter().flatten().filter(|&&b| b == a).count();
+        if count != rows {
+            return Err(Error::no_solution(format!(
+                "there are {} {}s for rooms {} deep",
+                count,
+                letter(a),
+                rows
+            )));
+        }
+    }
+    Ok(burrow)
+}
+
+/// The least energy it takes to sort the amphipods. If asked for the moves, the answer is
+/// the energy on its first line, then the burrow at each step of the way
+fn organize(burrow: &Burrow, params: &Params) -> Result<Answer> {
+    let (path, energy) = astar(burrow, Burrow::moves, Burrow::estimate, Burrow::is_sorted)
+        .ok_or_else(|| Error::no_solution("the amphipods can't be sorted"))?;
+    if !params.flag("moves") {
+        return Ok(energy.into());
+    }
+    let steps = path.iter().map(Burrow::to_string).collect::<Vec<_>>();
+    Ok(format!("{}\n\n{}", energy, steps.join("\n\n")).into())
+}
+
+impl Solution for Code {
+    type Parsed = Burrow;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Burrow> {
+        parse_burrow(input)
+    }
+
+    fn part1(&self, burrow: &Burrow, params: &Params) -> Result<Answer> {
+        organize(burrow, params)
+    }
+
+    fn part2(&self, burrow: &Burrow, params: &Params) -> Result<Answer> {
+        organize(&burrow.unfolded()?, params)
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+}
@@ -0,0 +1,28 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::params::Params;
+use crate::Solution;
+
+pub struct Code;
+
+crate::register!(2021, 24);
+
+impl Solution for Code {
+    type Parsed = ();
+
+    fn parse(&self, _input: &str, _params: &Params) -> Result<()> {
+        Err(Error::unimplemented())
+    }
+
+    fn implemented(&self) -> bool {
+        false
+    }
+
+    fn part1(&self, _parsed: &(), _params: &Params) -> Result<Answer> {
+        Err(Error::unimplemented())
+    }
+
+    fn part2(&self, _parsed: &(), _params: &Params) -> Result<Answer> {
+        Err(Error::unimplemented())
+    }
+}
@@ -0,0 +1,148 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::params::Params;
+use crate::Solution;
+
+pub struct Code;
+
+crate::register!(2021, 3);
+
+/// The diagnostic numbers, and how many zeros and ones there are at each position
+pub struct Report {
+    numbers: Vec<String>,
+    counts: Vec<(u64, u64)>,
+}
+
+#[inline(always)]
+fn get_counts(input: &str) -> Result<Vec<(u64, u64)>> {
+    let width = input.lines().next().map_or(0, str::len);
+    let mut counts = Vec::with_capacity(width);
+    for _ in 0..width {
+        counts.push((0, 0));
+    }
+    for (line_index, line) in input.lines().enumerate() {
+        if line.len() != width {
+            return Err(Error::parse(
+                line_index + 1,
+                line,
+                format!("expected {} bits", width),
+            ));
+        }
+        for (index, digit) in line.chars().enumerate() {
+            match digit {
+                '0' => counts[index].0 += 1,
+                '1' => counts[index].1 += 1,
+                _ => {
+                    return Err(Error::parse(
+                        line_index + 1,
+                        line,
+                        "expected a binary number",
+                    ))
+                }
+            }
+        }
+    }
+    if counts.is_empty() {
+        return Err(Error::no_solution("there are no diagnostic numbers"));
+    }
+    Ok(counts)
+}
+
+#[inline(always)]
+fn calculate_rating(
+    input: &[String],
+    counts: &[(u64, u64)],
+    lt: bool,
+    gt: bool,
+    eq: bool,
+) -> Result<u64> {
+    let mut found = false;
+    let mut current_index = 0;
+    let mut input = input.iter().map(String::as_str).collect::<Vec<_>>();
+    let mut counts = counts.to_vec();
+
+    // Duplicate numbers can't be told apart, so stop once every bit has been looked at
+    while !found && current_index < counts.len() {
+        let mut len = input.len();
+        let mut next_counts = counts.to_vec();
+        input = input
+            .into_iter()
+            .filter(|line| {
+                let char = line.chars().nth(current_index).unwrap();
+                let (zero_count, one_count) = counts[current_index];
+                let filter_pass = if zero_count > one_count {
+                    if char == '0' {
+                        lt
+                    } else {
+                        !lt
+                    }
+                } else if zero_count == one_count {
+                    if char == '0' {
+                        eq
+                    } else {
+                        !eq
+                    }
+                } else if char == '0' {
+                    gt
+                } else {
+                    !gt
+                };
+                if !filter_pass && len > 1 {
+                    len -= 1;
+                    for (index, char) in line.chars().enumerate() {
+                        if char == '0' {
+                            next_counts[index].0 -= 1;
+                        } else {
+                            next_counts[index].1 -= 1;
+                        }
+                    }
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect::<Vec<_>>();
+        if input.len() <= 1 {
+            found = true
+        }
+        current_index += 1;
+        counts = next_counts;
+    }
+    u64::from_str_radix(input[0], 2).map_err(Error::no_solution)
+}
+
+impl Solution for Code {
+    type Parsed = Report;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Report> {
+        let counts = get_counts(input)?;
+        let numbers = input.lines().map(str::to_string).collect();
+        Ok(Report { numbers, counts })
+    }
+
+    fn part1(&self, report: &Report, _params: &Params) -> Result<Answer> {
+        let counts = &report.counts;
+        let gamma = counts
+            .iter()
+            .map(|x| if x.0 < x.1 { "0" } else { "1" })
+            .collect::<String>();
+        let gamma = i64::from_str_radix(&gamma, 2).map_err(Error::no_solution)?;
+
+        let epsilon = counts
+            .iter()
+            .map(|x| if x.0 > x.1 { "0" } else { "1" })
+            .collect::<String>();
+        let epsilon = i64::from_str_radix(&epsilon, 2).map_err(Error::no_solution)?;
+
+        let answer = gamma * epsilon;
+        Ok(answer.into())
+    }
+
+    fn part2(&self, report: &Report, _params: &Params) -> Result<Answer> {
+        let o2 = calculate_rating(&report.numbers, &report.counts, true, false, false)?;
+        let co2 = calculate_rating(&report.numbers, &report.counts, false, true, true)?;
+
+        let answer = co2 * o2;
+        Ok(answer.into())
+    }
+}
@@ -1,10 +1,16 @@
-use crate::AoCDay;
+use crate::answer::Answer;
+use crate::error::{Error, LineContext, Result};
+use crate::params::Params;
+use crate::Solution;
 
 use nalgebra::{matrix, SMatrix};
 
 pub struct Code;
 
-struct Board {
+crate::register!(2021, 4);
+
+#[derive(Clone)]
+pub struct Board {
     board: SMatrix<i8, 5, 5>,
     row_counts: [u8; 5],
     column_counts: [u8; 5],
@@ -13,15 +19,7 @@ struct Board {
 impl Board {
     #[inline(always)]
     fn check(&self) -> bool {
-        matches!(
-            self.row_counts.iter().find(|&row_count| *row_count == 5),
-            Some(_)
-        ) || matches!(
-            self.column_counts
-                .iter()
-                .find(|&column_count| *column_count == 5),
-            Some(_)
-        )
+        self.row_counts.contains(&5) || self.column_counts.contains(&5)
     }
     #[inline(always)]
     fn sum_of_unmarked(&self) -> i32 {
@@ -59,9 +57,15 @@ fn mark_hits(boards: &mut [Board], number: i8) {
 }
 
 #[inline(always)]
-fn numbers_and_boards_from_input(input: &str) -> (&str, Vec<Board>) {
+fn numbers_and_boards_from_input(input: &str) -> Result<(Vec<i8>, Vec<Board>)> {
     let input = input.lines().collect::<Vec<_>>();
-    let numbers = input[0];
+    let numbers = input
+        .first()
+        .ok_or_else(|| Error::no_solution("there are no numbers to draw"))?;
+    let numbers = numbers
+        .split(',')
+        .map(|number| number.parse::<i8>().at_line(1, numbers))
+        .collect::<Result<Vec<_>>>()?;
     let mut boards = vec![];
     let mut board = matrix![
         0,0,0,0,0;
@@ -71,7 +75,12 @@ fn numbers_and_boards_from_input(input: &str) -> (&str, Vec<Board>) {
         0,0,0,0,0
     ];
     let mut row = 0;
-    for line in input.iter().skip(1).skip_while(|line| line.is_empty()) {
+    for (index, line) in input
+        .iter()
+        .enumerate()
+        .skip(1)
+        .skip_while(|(_, line)| line.is_empty())
+    {
         if line.is_empty() {
             boards.push(Board {
                 board,
@@ -87,43 +96,59 @@ fn numbers_and_boards_from_input(input: &str) -> (&str, Vec<Board>) {
             ];
             row = 0;
         } else {
+            if row == 5 {
+                return Err(Error::parse(index + 1, line, "boards have 5 rows"));
+            }
             for (column, num) in line
                 .trim()
                 .split(' ')
                 .filter(|num| !num.is_empty())
-                .map(|num| num.parse::<i8>().unwrap())
                 .enumerate()
             {
-                board[(row, column)] = num;
+                if column == 5 {
+                    return Err(Error::parse(index + 1, line, "boards have 5 columns"));
+                }
+                board[(row, column)] = num.parse::<i8>().at_line(index + 1, line)?;
             }
             row += 1;
         }
     }
-    (numbers, boards)
+    if row > 0 {
+        // The last board isn't followed by a blank line
+        boards.push(Board {
+            board,
+            row_counts: [0; 5],
+            column_counts: [0; 5],
+        });
+    }
+    Ok((numbers, boards))
 }
 
-impl AoCDay for Code {
-    fn part1(&self, input: &str, _extra_args: &[String]) -> String {
-        let (numbers, mut boards) = numbers_and_boards_from_input(input);
+impl Solution for Code {
+    type Parsed = (Vec<i8>, Vec<Board>);
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<(Vec<i8>, Vec<Board>)> {
+        numbers_and_boards_from_input(input)
+    }
+
+    fn part1(&self, (numbers, boards): &(Vec<i8>, Vec<Board>), _params: &Params) -> Result<Answer> {
+        let mut boards = boards.clone();
 
-        for number in numbers.split(',') {
-            let number = number.parse::<i8>().unwrap();
+        for &number in numbers {
             mark_hits(&mut boards, number);
             if let Some(sum_of_unmarked) = check_boards(&boards) {
                 let answer = sum_of_unmarked * i32::from(number);
-                debug_assert_eq!(answer, 38594);
-                return format!("{}", answer); // 38594/~250μs
+                return Ok(answer.into());
             }
         }
 
-        panic!("At the disco")
+        Err(Error::no_solution("no board ever wins"))
     }
 
-    fn part2(&self, input: &str, _extra_args: &[String]) -> String {
-        let (numbers, mut boards) = numbers_and_boards_from_input(input);
+    fn part2(&self, (numbers, boards): &(Vec<i8>, Vec<Board>), _params: &Params) -> Result<Answer> {
+        let mut boards = boards.clone();
 
-        for number in numbers.split(',') {
-            let number = number.parse::<i8>().unwrap();
+        for &number in numbers {
             mark_hits(&mut boards, number);
 
             let mut boards_len = boards.len();
@@ -139,25 +164,14 @@ impl AoCDay for Code {
                         }
                     })
                     .collect::<Vec<Board>>();
-            } else if boards[0].check() {
+            } else if boards.first().is_some_and(Board::check) {
                 let sum_of_unmarked = boards[0].sum_of_unmarked();
 
                 let answer = sum_of_unmarked * i32::from(number);
-                debug_assert_eq!(answer, 21184);
-                return format!("{}", answer); // 21184/~440μs
+                return Ok(answer.into());
             }
         }
 
-        panic!("At the disco")
-    }
-
-    fn both(&self, input: &str, extra_args: &[String]) -> String {
-        let p1 = self.part1(input, extra_args);
-        let p2 = self.part2(input, extra_args);
-        format!(
-            "Part1: {}\n\
-            Part2: {}",
-            p1, p2
-        )
+        Err(Error::no_solution("the last board never wins"))
     }
 }
@@ -1,15 +1,21 @@
-use crate::AoCDay;
+use crate::answer::Answer;
+use crate::error::{LineContext, Result};
+use crate::params::Params;
+use crate::Solution;
 
 use hashbrown::HashMap;
 
 use nom::{
     bytes::complete::tag,
     character::complete::{char, digit1, multispace1},
+    combinator::map_res,
     IResult,
 };
 
 pub struct Code;
 
+crate::register!(2021, 5);
+
 #[derive(Debug)]
 struct Coord {
     x: i32,
@@ -17,7 +23,7 @@ struct Coord {
 }
 
 #[derive(Debug)]
-struct Line {
+pub struct Line {
     start: Coord,
     end: Coord,
 }
@@ -54,40 +60,51 @@ impl CoordMap {
     }
 }
 
+#[inline(always)]
+fn parse_num(input: &str) -> IResult<&str, i32> {
+    map_res(digit1, |n: &str| n.parse::<i32>())(input)
+}
+
 #[inline(always)]
 fn parse_line(input: &str) -> IResult<&str, Line> {
-    let (input, x1) = digit1(input)?;
+    let (input, x1) = parse_num(input)?;
     let (input, _) = char(',')(input)?;
-    let (input, y1) = digit1(input)?;
+    let (input, y1) = parse_num(input)?;
 
     let (input, _) = multispace1(input)?;
     let (input, _) = tag("->")(input)?;
     let (input, _) = multispace1(input)?;
 
-    let (input, x2) = digit1(input)?;
+    let (input, x2) = parse_num(input)?;
     let (input, _) = char(',')(input)?;
-    let (input, y2) = digit1(input)?;
+    let (input, y2) = parse_num(input)?;
 
     Ok((
         input,
         Line {
-            start: Coord {
-                x: x1.parse::<i32>().unwrap(),
-                y: y1.parse::<i32>().unwrap(),
-            },
-            end: Coord {
-                x: x2.parse::<i32>().unwrap(),
-                y: y2.parse::<i32>().unwrap(),
-            },
+            start: Coord { x: x1, y: y1 },
+            end: Coord { x: x2, y: y2 },
         },
     ))
 }
 
-impl AoCDay for Code {
-    fn part1(&self, input: &str, _extra_args: &[String]) -> String {
+impl Solution for Code {
+    type Parsed = Vec<Line>;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Vec<Line>> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                let (_, line) = parse_line(line).at_line(index + 1, line)?;
+                Ok(line)
+            })
+            .collect()
+    }
+
+    fn part1(&self, lines: &Vec<Line>, _params: &Params) -> Result<Answer> {
         let mut map = CoordMap::new();
-        for line in input.lines() {
-            let (_, line) = parse_line(line).unwrap();
+        for line in lines {
             if line.start.x == line.end.x {
                 let x = line.start.x;
                 let start = line.start.y.min(line.end.y);
@@ -105,14 +122,12 @@ impl AoCDay for Code {
             }
         }
         let answer = map.count_of_intersections();
-        debug_assert_eq!(answer, 6007);
-        format!("{}", answer) // 6007/~4200μs
+        Ok(answer.into())
     }
 
-    fn part2(&self, input: &str, _extra_args: &[String]) -> String {
+    fn part2(&self, lines: &Vec<Line>, _params: &Params) -> Result<Answer> {
         let mut map = CoordMap::new();
-        for line in input.lines() {
-            let (_, line) = parse_line(line).unwrap();
+        for line in lines {
             if line.start.x == line.end.x {
                 let x = line.start.x;
                 let start = line.start.y.min(line.end.y);
@@ -129,9 +144,9 @@ impl AoCDay for Code {
                 }
             } else {
                 let (start, end) = if line.start.x < line.end.x {
-                    (line.start, line.end)
+                    (&line.start, &line.end)
                 } else {
-                    (line.end, line.start)
+                    (&line.end, &line.start)
                 };
                 let slope = (end.y - start.y) / (end.x - start.x);
                 let mut y = start.y;
@@ -143,7 +158,6 @@ impl AoCDay for Code {
             }
         }
         let answer = map.count_of_intersections();
-        debug_assert_eq!(answer, 19349);
-        format!("{}", answer) // 19349/~8500μs
+        Ok(answer.into())
     }
 }
@@ -0,0 +1,67 @@
+use crate::answer::Answer;
+use crate::error::{Error, LineContext, Result};
+use crate::params::{Param, Params};
+use crate::Solution;
+
+pub struct Code;
+
+crate::register!(2021, 6);
+
+const PARAMS: &[Param] = &[Param::int("days", "Days to simulate", 80)
+    .part2(256)
+    .range(0, 1_000)];
+
+/// How many fish there are with each timer
+type School = [u64; 9];
+
+fn parse_the_fishies(input: &str) -> Result<School> {
+    let mut the_fishies = [0; 9];
+    for num in input.trim().split(',') {
+        let num_days = num.parse::<usize>().at_line(1, num)?;
+        if num_days >= the_fishies.len() {
+            return Err(Error::parse(1, num, "timers run from 0 to 8"));
+        }
+        the_fishies[num_days] += 1;
+    }
+    Ok(the_fishies)
+}
+
+#[inline(always)]
+fn count_the_fishies(the_fishies: &School, days: i64) -> Result<u64> {
+    let too_many = || Error::argument(format!("too many fish to count after {} days", days));
+    let mut the_fishies = *the_fishies;
+    for _ in 0..days {
+        let zero = the_fishies[0];
+        the_fishies[0] = 0;
+        the_fishies.rotate_left(1);
+        the_fishies[6] = the_fishies[6].checked_add(zero).ok_or_else(too_many)?;
+        the_fishies[8] = zero;
+    }
+
+    the_fishies
+        .iter()
+        .try_fold(0u64, |total, &n| total.checked_add(n))
+        .ok_or_else(too_many)
+}
+
+impl Solution for Code {
+    type Parsed = School;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<School> {
+        parse_the_fishies(input)
+    }
+
+    fn part1(&self, the_fishies: &School, params: &Params) -> Result<Answer> {
+        let total = count_the_fishies(the_fishies, params.int_in("days", 1))?;
+        Ok(total.into())
+    }
+
+    fn part2(&self, the_fishies: &School, params: &Params) -> Result<Answer> {
+        let total = count_the_fishies(the_fishies, params.int_in("days", 2))?;
+        Ok(total.into())
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+}
@@ -0,0 +1,46 @@
+use crate::answer::Answer;
+use crate::error::{LineContext, Result};
+use crate::params::Params;
+use crate::Solution;
+
+pub struct Code;
+
+crate::register!(2021, 7);
+
+fn fuel_cost(x: i64, y: i64) -> i64 {
+    let n = (x - y).abs();
+    (n * (n + 1)) / 2
+}
+
+impl Solution for Code {
+    type Parsed = Vec<i64>;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Vec<i64>> {
+        input
+            .trim()
+            .split(',')
+            .map(|num| num.parse::<i64>().at_line(1, num))
+            .collect()
+    }
+
+    fn part1(&self, inputs: &Vec<i64>, _params: &Params) -> Result<Answer> {
+        let mut inputs = inputs.clone();
+
+        inputs.sort_unstable();
+        let index = inputs.len() / 2;
+        let median = inputs[index];
+        let answer: i64 = inputs.iter().map(|num| (num - median).abs()).sum();
+        Ok(answer.into())
+    }
+
+    fn part2(&self, inputs: &Vec<i64>, _params: &Params) -> Result<Answer> {
+        let mean = inputs.iter().sum::<i64>() / inputs.len() as i64;
+        let answer: i64 = [mean - 1, mean, mean + 1]
+            .iter()
+            .map(|x| inputs.iter().map(|y| fuel_cost(*x, *y)).sum())
+            .min()
+            .unwrap();
+
+        Ok(answer.into())
+    }
+}
@@ -1,12 +1,17 @@
 use std::iter::FromIterator;
 
-use crate::AoCDay;
+use crate::answer::Answer;
+use crate::error::{Error, LineContext, Result};
+use crate::params::Params;
+use crate::Solution;
 
 use hashbrown::HashMap;
 use hashbrown::HashSet;
 
 pub struct Code;
 
+crate::register!(2021, 8);
+
 fn normalize_signal(signal: &str) -> String {
     let mut signal = signal.chars().collect::<Vec<_>>();
     signal.sort_unstable();
@@ -18,6 +23,20 @@ fn digit_to_set(digit: &str) -> HashSet<char> {
     HashSet::from_iter(chars)
 }
 
+#[inline(always)]
+fn split_entry(line_number: usize, line: &str) -> Result<(&str, &str)> {
+    line.split_once(" | ")
+        .ok_or_else(|| Error::parse(line_number, line, "expected patterns | output"))
+}
+
+/// One line of the input, with every signal normalized
+pub struct Entry {
+    line_number: usize,
+    line: String,
+    patterns: Vec<String>,
+    outputs: Vec<String>,
+}
+
 struct DigitMap {
     mappings: HashMap<String, i32>,
     reverse_mappings: HashMap<i32, String>,
@@ -49,35 +68,43 @@ impl DigitMap {
     }
 }
 
-impl AoCDay for Code {
-    fn part1(&self, input: &str, _extra_args: &[String]) -> String {
+impl Solution for Code {
+    type Parsed = Vec<Entry>;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Vec<Entry>> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                let (patterns, outputs) = split_entry(index + 1, line)?;
+                Ok(Entry {
+                    line_number: index + 1,
+                    line: line.to_string(),
+                    patterns: patterns.split(' ').map(normalize_signal).collect(),
+                    outputs: outputs.split(' ').map(normalize_signal).collect(),
+                })
+            })
+            .collect()
+    }
+
+    fn part1(&self, entries: &Vec<Entry>, _params: &Params) -> Result<Answer> {
         let mut count = 0;
-        for line in input.lines() {
-            let input = line.split(" | ").collect::<Vec<_>>();
-            let output = input[1].split(' ').collect::<Vec<_>>();
-            count += output
+        for entry in entries {
+            count += entry
+                .outputs
                 .iter()
                 .filter(|x| matches!(x.len(), 2 | 4 | 3 | 7))
                 .count();
         }
-        debug_assert_eq!(count, 303);
-        format!("{}", count) // 303/~175μs
+        Ok(count.into())
     }
 
-    fn part2(&self, input: &str, _extra_args: &[String]) -> String {
+    fn part2(&self, entries: &Vec<Entry>, _params: &Params) -> Result<Answer> {
         let mut total = 0;
         let mut mapping = DigitMap::new();
-        for line in input.lines() {
+        for entry in entries {
             mapping.clear();
-            let _input = line.split(" | ").collect::<Vec<_>>();
-            let inputs = _input[0]
-                .split(' ')
-                .map(normalize_signal)
-                .collect::<Vec<_>>();
-            let outputs = _input[1]
-                .split(' ')
-                .map(normalize_signal)
-                .collect::<Vec<_>>();
+            let inputs = &entry.patterns;
 
             let one = inputs.iter().find(|x| x.len() == 2);
             if let Some(one) = one {
@@ -153,14 +180,16 @@ impl AoCDay for Code {
                 }
             }
             let mut thingy = vec![];
-            for digit in outputs {
-                if let Some(n) = mapping.get_digit(&normalize_signal(&digit)) {
+            for digit in &entry.outputs {
+                if let Some(n) = mapping.get_digit(digit) {
                     thingy.push(n.to_string());
                 }
             }
-            total += thingy.join("").parse::<i32>().unwrap();
+            total += thingy
+                .join("")
+                .parse::<i32>()
+                .at_line(entry.line_number, &entry.line)?;
         }
-        debug_assert_eq!(total, 961734);
-        format!("{:?}", total) // 961734/~5600μs
+        Ok(total.into())
     }
 }
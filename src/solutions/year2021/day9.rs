@@ -1,32 +1,55 @@
-use crate::AoCDay;
+use std::str::FromStr;
+
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::params::Params;
+use crate::Solution;
 
 pub struct Code;
 
-struct Mapping {
+crate::register!(2021, 9);
+
+#[derive(Clone)]
+pub struct Mapping {
     map: Vec<i32>,
     x_size: usize,
     y_size: usize,
 }
 
-impl Mapping {
+impl FromStr for Mapping {
+    type Err = Error;
+
     #[inline(always)]
-    fn from_str(input: &str) -> Self {
+    fn from_str(input: &str) -> Result<Self> {
         let mut map = Vec::with_capacity(100 * 100);
         let mut y_size = 0;
         let mut x_size = 0;
         for (y, line) in input.lines().enumerate() {
             y_size = y_size.max(y + 1);
+            if y > 0 && line.len() != x_size {
+                return Err(Error::parse(
+                    y + 1,
+                    line,
+                    format!("expected {} digits", x_size),
+                ));
+            }
             for (x, num) in line.chars().enumerate() {
                 x_size = x_size.max(x + 1);
-                map.push(num.to_string().parse::<i32>().unwrap());
+                let num = num.to_digit(10).ok_or_else(|| {
+                    Error::parse(y + 1, line, format!("{:?} is not a digit", num))
+                })?;
+                map.push(num as i32);
             }
         }
-        Mapping {
+        Ok(Mapping {
             map,
             x_size,
             y_size,
-        }
+        })
     }
+}
+
+impl Mapping {
     #[inline(always)]
     fn get(&self, x: usize, y: usize) -> Option<i32> {
         if x >= self.x_size || y >= self.y_size {
@@ -86,9 +109,7 @@ impl Mapping {
         for ((x, y), _) in low_points {
             let mut stack = vec![(x, y)];
             let mut current_basin_size = 0;
-            while !stack.is_empty() {
-                let (x, y) = stack.pop().unwrap();
-
+            while let Some((x, y)) = stack.pop() {
                 if let Some(num) = self.get(x, y) {
                     if num == -1 {
                         continue;
@@ -132,20 +153,20 @@ impl Mapping {
     }
 }
 
-impl AoCDay for Code {
-    fn part1(&self, input: &str, _extra_args: &[String]) -> String {
-        let answer: i32 = Mapping::from_str(input)
-            .get_low_points()
-            .iter()
-            .map(|(_, x)| 1 + *x)
-            .sum();
-        debug_assert_eq!(answer, 591);
-        format!("{}", answer) // 591/~800μs
+impl Solution for Code {
+    type Parsed = Mapping;
+
+    fn parse(&self, input: &str, _params: &Params) -> Result<Mapping> {
+        Mapping::from_str(input)
+    }
+
+    fn part1(&self, map: &Mapping, _params: &Params) -> Result<Answer> {
+        let answer: i32 = map.get_low_points().iter().map(|(_, x)| 1 + *x).sum();
+        Ok(answer.into())
     }
 
-    fn part2(&self, input: &str, _extra_args: &[String]) -> String {
-        let answer = Mapping::from_str(input).get_product_of_top_three_basins_by_size();
-        debug_assert_eq!(answer, 1113424);
-        format!("{:?}", answer) // 1113424/~1200μs
+    fn part2(&self, map: &Mapping, _params: &Params) -> Result<Answer> {
+        let answer = map.clone().get_product_of_top_three_basins_by_size();
+        Ok(answer.into())
     }
 }
@@ -0,0 +1,209 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{bail, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use structopt::clap::AppSettings;
+use structopt::StructOpt;
+use time::OffsetDateTime;
+
+use aoc2021::answer::Answer;
+use aoc2021::day::{Day, Year};
+
+use crate::client::{Client, Verdict};
+use crate::fetch::cached_input;
+use crate::run::read_input;
+use crate::verify::AnswerStore;
+
+#[derive(StructOpt)]
+#[structopt(setting = AppSettings::AllowMissingPositional)]
+pub struct Submit {
+    /// Event year, defaults to 2021
+    year: Option<Year>,
+    /// Problem day to submit
+    day: Day,
+    /// Part to submit
+    #[structopt(long, short)]
+    part: u8,
+    /// Guess history, defaults to $AOC_INPUT/history.toml
+    #[structopt(long)]
+    history: Option<PathBuf>,
+}
+
+/// One submitted answer
+#[derive(Debug, Serialize, Deserialize)]
+struct Guess {
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: String,
+    verdict: Verdict,
+    /// Unix timestamp
+    at: i64,
+}
+
+/// Every answer ever submitted, so that a guess that's known to be wrong isn't sent again
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct History {
+    #[serde(default, rename = "guess")]
+    guesses: Vec<Guess>,
+}
+
+impl History {
+    fn open(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Could not read {}", path.display()))?;
+        toml::from_str(&contents).wrap_err_with(|| format!("Could not parse {}", path.display()))
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        let contents = toml::to_string(self)?;
+        fs::write(path, contents).wrap_err_with(|| format!("Could not write {}", path.display()))
+    }
+
+    /// Why `answer` can't be right, going by earlier verdicts
+    fn refusal(&self, day: Day, part: u8, answer: &str) -> Option<String> {
+        let number = answer.parse::<i128>().ok();
+        self.guesses
+            .iter()
+            .filter(|guess| {
+                guess.year == day.year().get() && guess.day == day.get() && guess.part == part
+            })
+            .find_map(|guess| {
+                let previous = guess.answer.parse::<i128>().ok();
+                match (guess.verdict, number, previous) {
+                    (Verdict::Right, _, _) => {
+                        Some(format!("it was already solved with {}", guess.answer))
+                    }
+                    (Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong, _, _)
+                        if guess.answer == answer =>
+                    {
+                        Some(format!("{} was already wrong", answer))
+                    }
+                    (Verdict::TooHigh, Some(number), Some(previous)) if number >= previous => {
+                        Some(format!("{} was already too high", previous))
+                    }
+                    (Verdict::TooLow, Some(number), Some(previous)) if number <= previous => {
+                        Some(format!("{} was already too low", previous))
+                    }
+                    _ => None,
+                }
+            })
+    }
+}
+
+impl Submit {
+    pub fn submit(&self) -> Result<String> {
+        if !(1..=2).contains(&self.part) {
+            bail!("--part needs to be 1 or 2");
+        }
+        let history_path = match self.history {
+            Some(ref path) => path.clone(),
+            None => {
+                let dir = env::var("AOC_INPUT").wrap_err("AOC_INPUT is not set")?;
+                Path::new(&dir).join("history.toml")
+            }
+        };
+        let mut history = History::open(&history_path)?;
+        let day = self.day.in_year(self.year);
+
+        let path = cached_input(day)?;
+        let answer = aoc2021::solve(day, self.part, &read_input(&path)?, &[])?;
+        // The drawing itself is never what the website expects
+        if let Answer::Grid(grid) = &answer {
+            if grid.ocr().is_none() {
+                bail!("Not submitting, the letters can't be read:\n{}", grid);
+            }
+        }
+        let answer = answer.to_submission();
+        if let Some(reason) = history.refusal(day, self.part, &answer) {
+            bail!("Not submitting {}, {}", answer, reason);
+        }
+
+        let (verdict, message) = Client::from_env()?.submit(day, self.part, &answer)?;
+        history.guesses.push(Guess {
+            year: day.year().get(),
+            day: day.get(),
+            part: self.part,
+            answer: answer.clone(),
+            verdict,
+            at: OffsetDateTime::now_utc().unix_timestamp(),
+        });
+        history.save(&history_path)?;
+
+        if verdict == Verdict::Right {
+            let mut store = AnswerStore::open(None)?;
+            let key = store.key_for(&path);
+            store.record(&key, day, self.part, answer.clone());
+            store.save()?;
+        }
+        Ok(format!(
+            "Day {} part {}: {}\n{}",
+            day.get(),
+            self.part,
+            answer,
+            message
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guess(day: u8, part: u8, answer: &str, verdict: Verdict) -> Guess {
+        Guess {
+            year: Year::DEFAULT.get(),
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            at: 0,
+        }
+    }
+
+    fn day(day: u8) -> Day {
+        Day::new(Year::DEFAULT, day).unwrap()
+    }
+
+    #[test]
+    fn refusal() {
+        let history = History {
+            guesses: vec![
+                guess(1, 1, "100", Verdict::TooHigh),
+                guess(1, 1, "10", Verdict::TooLow),
+                guess(1, 1, "50", Verdict::Wrong),
+                guess(1, 1, "42", Verdict::RateLimited),
+                guess(2, 1, "7", Verdict::Right),
+            ],
+        };
+        let refusal = |d, part, answer| history.refusal(day(d), part, answer);
+        assert_eq!(
+            refusal(1, 1, "100"),
+            Some("100 was already wrong".to_string())
+        );
+        assert_eq!(
+            refusal(1, 1, "150"),
+            Some("100 was already too high".to_string())
+        );
+        assert_eq!(
+            refusal(1, 1, "5"),
+            Some("10 was already too low".to_string())
+        );
+        assert_eq!(
+            refusal(1, 1, "50"),
+            Some("50 was already wrong".to_string())
+        );
+        assert_eq!(refusal(1, 1, "42"), None);
+        assert_eq!(refusal(1, 1, "60"), None);
+        assert_eq!(refusal(1, 2, "150"), None);
+        assert_eq!(
+            refusal(2, 1, "8"),
+            Some("it was already solved with 7".to_string())
+        );
+    }
+}
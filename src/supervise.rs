@@ -0,0 +1,298 @@
+//! Runs days in a worker process of their own, so that one that never finishes can be killed
+//! instead of hanging the whole run, and so that its peak memory is its own
+
+use std::env;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use structopt::StructOpt;
+
+use aoc2021::day::{Day, Part, Year};
+use aoc2021::params::Params;
+
+use crate::bench::Bench;
+use crate::format::Record;
+use crate::run::{format_answer, parse_timed};
+
+/// How often a worker with a timeout is checked on
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(StructOpt)]
+pub struct Supervision {
+    /// Kill a day that runs for longer than this, e.g. 30s or 500ms, and report it as a timeout
+    #[structopt(long)]
+    timeout: Option<Timeout>,
+    /// Report the peak memory of each day. This and --timeout run each day in a process of
+    /// its own
+    #[structopt(long)]
+    pub memory: bool,
+}
+
+/// A duration given as a number with a unit of ms, s or m. A bare number is in seconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeout(Duration);
+
+impl FromStr for Timeout {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = "Timeout needs to be a duration like 30s, 500ms or 2m";
+        let split = s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len());
+        let (number, unit) = s.split_at(split);
+        let number = number.parse::<f64>().map_err(|_| invalid)?;
+        let seconds = match unit {
+            "ms" => number / 1_000.0,
+            "" | "s" => number,
+            "m" => number * 60.0,
+            _ => return Err(invalid),
+        };
+        if !seconds.is_finite() || seconds <= 0.0 {
+            return Err(invalid);
+        }
+        Ok(Timeout(Duration::from_secs_f64(seconds)))
+    }
+}
+
+impl Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+/// The error of a day that was killed for running too long
+#[derive(Debug)]
+pub struct TimedOut(pub Timeout);
+
+impl Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TIMEOUT after {}", self.0)
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// Internal: solves one day with the input from stdin and prints a `Report`, for
+/// `--timeout` and `--memory`
+#[derive(StructOpt)]
+pub struct Worker {
+    year: Year,
+    day: Day,
+    #[structopt(long)]
+    part: Part,
+    #[structopt(long)]
+    variant: String,
+    #[structopt(long)]
+    extra: Vec<String>,
+}
+
+/// What a worker sends back
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Report {
+    parts: Vec<PartReport>,
+    /// In KiB, where the system tells
+    peak_memory_kb: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PartReport {
+    record: Record,
+    /// The answer as `run` prints it, if there is one
+    text: Option<String>,
+}
+
+/// The most memory this process has had at once, which Linux keeps as VmHWM
+fn peak_memory_kb() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?;
+    line.trim().strip_suffix("kB")?.trim().parse().ok()
+}
+
+/// KiB, in whichever unit reads best
+pub fn format_memory(kb: u64) -> String {
+    if kb >= 1024 * 1024 {
+        format!("{:.2}GiB", kb as f64 / (1024.0 * 1024.0))
+    } else if kb >= 1024 {
+        format!("{:.1}MiB", kb as f64 / 1024.0)
+    } else {
+        format!("{}KiB", kb)
+    }
+}
+
+impl Worker {
+    /// The report, as JSON
+    pub fn work(&self) -> Result<String> {
+        let day = self.day.in_year(Some(self.year));
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .wrap_err("Could not read stdin")?;
+        let code = day.get_variant(&self.variant).map_err(|e| eyre!(e))?;
+        let params = Params::parse(code.params(), &self.extra)?;
+
+        let (parsed, parse_time) = parse_timed(code, day, &input, &params);
+        let parts = self
+            .part
+            .numbers()
+            .iter()
+            .map(|&part| {
+                let start = time::Instant::now();
+                let result = match parsed {
+                    Ok(ref parsed) => code
+                        .solve(parsed, part, &params)
+                        .map_err(|e| e.in_day(day.get()).in_part(part)),
+                    Err(ref e) => Err(e.clone()),
+                };
+                let time = time::Instant::now() - start;
+                PartReport {
+                    record: Record::new(day, part, &result, parse_time, time),
+                    text: result.ok().map(|answer| format_answer(&answer)),
+                }
+            })
+            .collect();
+        let report = Report {
+            parts,
+            peak_memory_kb: peak_memory_kb(),
+        };
+        Ok(serde_json::to_string(&report)?)
+    }
+}
+
+impl Report {
+    /// With the peak memory when it was asked for
+    pub fn records(&self, memory: bool) -> Vec<Record> {
+        self.parts
+            .iter()
+            .map(|part| Record {
+                peak_memory_kb: self.peak_memory_kb.filter(|_| memory),
+                ..part.record.clone()
+            })
+            .collect()
+    }
+
+    /// The answers as `run` prints them, with `PartN: ` in front of each when `labelled`.
+    /// Fails with the error of the first part that failed
+    pub fn output(&self, labelled: bool) -> Result<String> {
+        let mut lines = vec![];
+        for part in &self.parts {
+            let text = match (&part.text, &part.record.error) {
+                (Some(text), _) => text,
+                (None, Some(error)) => bail!("{}", error),
+                (None, None) => bail!(
+                    "Day {} part {}: {}",
+                    part.record.day,
+                    part.record.part,
+                    part.record.status
+                ),
+            };
+            match labelled {
+                true => lines.push(format!("Part{}: {}", part.record.part, text)),
+                false => lines.push(text.clone()),
+            }
+        }
+        Ok(lines.join("\n"))
+    }
+
+    /// Time spent parsing, and parsing and solving altogether
+    pub fn times(&self) -> (time::Duration, time::Duration) {
+        let micros = |us: f64| time::Duration::seconds_f64(us / 1_000_000.0);
+        let parse = self.parts.first().map_or(0.0, |part| part.record.parse_us);
+        let solve = self
+            .parts
+            .iter()
+            .map(|part| part.record.time_us)
+            .sum::<f64>();
+        (micros(parse), micros(parse + solve))
+    }
+
+    pub fn peak_memory_kb(&self) -> Option<u64> {
+        self.peak_memory_kb
+    }
+}
+
+impl Supervision {
+    pub fn enabled(&self) -> bool {
+        self.timeout.is_some() || self.memory
+    }
+
+    /// Benchmarks run many times over, which a worker can't report
+    pub fn check(&self, bench: &Bench) -> Result<()> {
+        if self.enabled() && bench.bench {
+            bail!("--timeout and --memory can't be combined with --bench");
+        }
+        Ok(())
+    }
+
+    /// Solves `day` in a worker, killing it if it runs out of time. A timeout is an error
+    /// that downcasts to `TimedOut`
+    pub fn run(
+        &self,
+        day: Day,
+        part: Part,
+        variant: &str,
+        extra: &[String],
+        input: &str,
+    ) -> Result<Report> {
+        let mut worker = Command::new(env::current_exe()?);
+        worker
+            .arg("worker")
+            .arg(day.year().to_string())
+            .arg(day.get().to_string())
+            .arg("--part")
+            .arg(part.to_string())
+            .arg("--variant")
+            .arg(variant);
+        for extra in extra {
+            worker.arg("--extra").arg(extra);
+        }
+        let mut child = worker
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .wrap_err("Could not start a worker")?;
+
+        // Both pipes are serviced on threads of their own, so that neither a large input
+        // nor a large report can block the worker while it's being waited on
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let input = input.to_string();
+        thread::spawn(move || stdin.write_all(input.as_bytes()));
+        let mut stdout = child.stdout.take().expect("stdout is piped");
+        let reader = thread::spawn(move || {
+            let mut report = String::new();
+            stdout.read_to_string(&mut report).map(|_| report)
+        });
+
+        let status = match self.timeout {
+            None => child.wait()?,
+            Some(timeout) => {
+                let deadline = Instant::now() + timeout.0;
+                loop {
+                    if let Some(status) = child.try_wait()? {
+                        break status;
+                    }
+                    if Instant::now() >= deadline {
+                        child.kill()?;
+                        child.wait()?;
+                        return Err(TimedOut(timeout).into());
+                    }
+                    thread::sleep(POLL_INTERVAL);
+                }
+            }
+        };
+        if !status.success() {
+            bail!("Day {}: the worker failed with {}", day.get(), status);
+        }
+        let report = reader
+            .join()
+            .map_err(|_| eyre!("Could not read the worker's report"))??;
+        serde_json::from_str(&report).wrap_err("Could not parse the worker's report")
+    }
+}
@@ -0,0 +1,313 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use color_eyre::eyre::{bail, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use structopt::clap::AppSettings;
+use structopt::StructOpt;
+
+use aoc2021::day::{Day, Part, Year};
+use aoc2021::error::ErrorKind;
+
+use crate::fetch::is_cached;
+use crate::run::{input_path, read_input};
+
+/// Answers confirmed for one input file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Known {
+    /// Only needed when the file isn't named `DayN`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    year: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    day: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part2: Option<String>,
+}
+
+/// Confirmed answers, keyed by input file relative to the store (`answers.toml` next to
+/// the inputs by default)
+pub struct AnswerStore {
+    path: PathBuf,
+    inputs: BTreeMap<String, Known>,
+}
+
+impl AnswerStore {
+    /// A missing file is an empty store
+    pub fn open(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => {
+                let dir = env::var("AOC_INPUT").wrap_err("AOC_INPUT is not set")?;
+                Path::new(&dir).join("answers.toml")
+            }
+        };
+        let inputs = if path.exists() {
+            let contents = fs::read_to_string(&path)
+                .wrap_err_with(|| format!("Could not read {}", path.display()))?;
+            toml::from_str(&contents)
+                .wrap_err_with(|| format!("Could not parse {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self { path, inputs })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let contents = toml::to_string(&self.inputs)?;
+        fs::write(&self.path, contents)
+            .wrap_err_with(|| format!("Could not write {}", self.path.display()))
+    }
+
+    fn dir(&self) -> &Path {
+        self.path.parent().unwrap_or_else(|| Path::new("."))
+    }
+
+    /// Inputs next to the store are keyed by file name, anything else by its full path
+    pub fn key_for(&self, input: &Path) -> String {
+        match input.strip_prefix(self.dir()) {
+            Ok(relative) => relative.display().to_string(),
+            Err(_) => input.display().to_string(),
+        }
+    }
+
+    pub fn get(&self, key: &str, part: u8) -> Option<&str> {
+        let known = self.inputs.get(key)?;
+        match part {
+            1 => known.part1.as_deref(),
+            _ => known.part2.as_deref(),
+        }
+    }
+
+    /// Returns the answer that was replaced, if there was one
+    pub fn record(&mut self, key: &str, day: Day, part: u8, answer: String) -> Option<String> {
+        let known = self.inputs.entry(key.to_string()).or_default();
+        if day_from_key(key) != Some(day) {
+            known.day = Some(day.get());
+            if day.year() != Year::DEFAULT {
+                known.year = Some(day.year().get());
+            }
+        }
+        match part {
+            1 => known.part1.replace(answer),
+            _ => known.part2.replace(answer),
+        }
+    }
+
+    /// Every input with a recorded answer, plus every `DayN` and `<year>/DayN` file next to
+    /// the store that isn't an empty placeholder
+    fn inputs(&self) -> Result<Vec<(Day, String)>> {
+        let mut inputs = BTreeMap::new();
+        for (key, known) in &self.inputs {
+            let year = known
+                .year
+                .and_then(|year| Year::from_str(&year.to_string()).ok());
+            let day = match known.day {
+                Some(day) => Day::from_str(&day.to_string())
+                    .ok()
+                    .map(|day| day.in_year(year)),
+                None => day_from_key(key),
+            };
+            match day {
+                Some(day) => inputs.insert(key.clone(), day),
+                None => bail!("Can't tell which day {} is for, set `day`", key),
+            };
+        }
+        let mut files = vec![];
+        if let Ok(entries) = fs::read_dir(self.dir()) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.parse::<Year>().is_err() {
+                    if is_cached(&entry.path()) {
+                        files.push(name);
+                    }
+                } else if let Ok(entries) = fs::read_dir(entry.path()) {
+                    for entry in entries.flatten() {
+                        if is_cached(&entry.path()) {
+                            files.push(format!("{}/{}", name, entry.file_name().to_string_lossy()));
+                        }
+                    }
+                }
+            }
+        }
+        for key in files {
+            if let Some(day) = day_from_key(&key) {
+                inputs.entry(key).or_insert(day);
+            }
+        }
+        let mut inputs = inputs
+            .into_iter()
+            .map(|(key, day)| (day, key))
+            .collect::<Vec<_>>();
+        inputs.sort_by_key(|(day, key)| (day.year(), day.get(), key.clone()));
+        Ok(inputs)
+    }
+}
+
+/// `DayN` files are for day N of the default year, `<year>/DayN` files for day N of that year
+fn day_from_key(key: &str) -> Option<Day> {
+    let (year, file) = match key.split_once('/') {
+        Some((year, file)) => (Some(year.parse().ok()?), file),
+        None => (None, key),
+    };
+    let day = file.strip_prefix("Day")?.parse::<Day>().ok()?;
+    Some(day.in_year(year))
+}
+
+#[derive(StructOpt)]
+pub struct Verify {
+    /// Answers file, defaults to $AOC_INPUT/answers.toml
+    #[structopt(long)]
+    answers: Option<PathBuf>,
+}
+
+#[derive(StructOpt)]
+#[structopt(setting = AppSettings::AllowMissingPositional)]
+pub struct Record {
+    /// Event year, defaults to 2021
+    year: Option<Year>,
+    /// Problem day
+    day: Day,
+    /// Path to input file
+    #[structopt(long)]
+    input: Option<PathBuf>,
+    /// Part to record
+    #[structopt(long, short, default_value)]
+    part: Part,
+    /// The confirmed answer, the solution's own answer is recorded when left out
+    #[structopt(long)]
+    answer: Option<String>,
+    /// Answers file, defaults to $AOC_INPUT/answers.toml
+    #[structopt(long)]
+    answers: Option<PathBuf>,
+}
+
+/// How an answer compares to the expected one
+pub enum Status {
+    Pass,
+    Fail,
+    Unknown,
+    Error,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Unknown => "unknown",
+            Status::Error => "ERROR",
+        }
+    }
+}
+
+impl Verify {
+    /// Returns the table, and how many answers failed or errored
+    pub fn verify(&self) -> Result<(String, usize)> {
+        let store = AnswerStore::open(self.answers.as_deref())?;
+        let mut rows = vec![[
+            "Input".to_string(),
+            "Day".to_string(),
+            "Part".to_string(),
+            "Expected".to_string(),
+            "Actual".to_string(),
+            "Status".to_string(),
+        ]];
+        let mut failures = 0;
+        for (day, key) in store.inputs()? {
+            let input = read_input(&store.dir().join(&key));
+            for part in 1..=2 {
+                let expected = store.get(&key, part);
+                let actual = match &input {
+                    Ok(input) => aoc2021::solve(day, part, input, &[])
+                        .map(|answer| answer.to_submission())
+                        .map_err(|e| (e.kind == ErrorKind::Unimplemented, e.to_string())),
+                    Err(e) => Err((false, format!("{:#}", e))),
+                };
+                let status = match (&actual, expected) {
+                    (Err((true, _)), _) => Status::Unknown,
+                    (Err(_), _) => Status::Error,
+                    (Ok(_), None) => Status::Unknown,
+                    (Ok(actual), Some(expected)) if actual == expected => Status::Pass,
+                    (Ok(_), Some(_)) => Status::Fail,
+                };
+                if matches!(status, Status::Fail | Status::Error) {
+                    failures += 1;
+                }
+                rows.push([
+                    key.clone(),
+                    day.get().to_string(),
+                    part.to_string(),
+                    expected.unwrap_or("-").to_string(),
+                    match actual {
+                        Ok(actual) => actual,
+                        Err((_, e)) => e,
+                    },
+                    status.as_str().to_string(),
+                ]);
+            }
+        }
+        Ok((table(&rows), failures))
+    }
+}
+
+/// Left aligned columns, wide enough for their longest cell
+pub fn table<const N: usize>(rows: &[[String; N]]) -> String {
+    let mut widths = [0; N];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl Record {
+    pub fn record(&self) -> Result<String> {
+        let parts = match (self.part, &self.answer) {
+            (Part::Part1, _) => vec![1],
+            (Part::Part2, _) => vec![2],
+            (Part::Both, None) => vec![1, 2],
+            (Part::Both, Some(_)) => bail!("--answer needs --part 1 or --part 2"),
+        };
+        let day = self.day.in_year(self.year);
+        let path = match self.input {
+            Some(ref path) => path.clone(),
+            None => input_path(day)?,
+        };
+        let mut store = AnswerStore::open(self.answers.as_deref())?;
+        let key = store.key_for(&path);
+
+        let mut output = vec![];
+        for part in parts {
+            let answer = match self.answer {
+                Some(ref answer) => answer.clone(),
+                None => aoc2021::solve(day, part, &read_input(&path)?, &[])?.to_submission(),
+            };
+            output.push(format!("{} part {}: {}", key, part, answer));
+            match store.record(&key, day, part, answer.clone()) {
+                Some(previous) if previous != answer => {
+                    output.push(format!("(was {})", previous));
+                }
+                _ => {}
+            }
+        }
+        store.save()?;
+        Ok(output.join("\n"))
+    }
+}
@@ -0,0 +1,86 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use color_eyre::eyre::{bail, Result};
+
+/// How often the watched files are checked
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Notices changes to files by polling their modification time
+pub struct Watcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+impl Watcher {
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let modified = modified(&path);
+                (path, modified)
+            })
+            .collect();
+        Watcher { files }
+    }
+
+    /// Blocks until at least one of the files changes, and returns the ones that did
+    pub fn wait(&mut self) -> Vec<PathBuf> {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let mut changed = vec![];
+            for (path, last_modified) in self.files.iter_mut() {
+                let modified = modified(path);
+                if modified != *last_modified {
+                    *last_modified = modified;
+                    changed.push(path.clone());
+                }
+            }
+            if !changed.is_empty() {
+                return changed;
+            }
+        }
+    }
+}
+
+/// Rebuilds the crate, then replaces this process with the new binary, run with the same
+/// arguments. Only returns if that fails, e.g. when the code doesn't compile, so the old
+/// code can keep watching
+pub fn rebuild_and_restart() -> Result<()> {
+    // Looked up before the build replaces the binary, as it can't be found after on Linux
+    let exe = env::current_exe()?;
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut build = Command::new(cargo);
+    build
+        .arg("build")
+        .arg("--manifest-path")
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"));
+    if !cfg!(debug_assertions) {
+        build.arg("--release");
+    }
+    if !build.status()?.success() {
+        bail!("The build failed, still running the previous code");
+    }
+
+    let mut restart = Command::new(exe);
+    restart.args(env::args_os().skip(1));
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        Err(restart.exec().into())
+    }
+    #[cfg(not(unix))]
+    {
+        let status = restart.status()?;
+        std::process::exit(status.code().unwrap_or(1));
+    }
+}
@@ -0,0 +1,211 @@
+//! Runs `fetch` and `submit` against a stand-in for the website, through `AOC_BASE_URL`
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+const SESSION: &str = "stand-in";
+const INPUT: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+
+/// A request the stand-in received
+#[derive(Debug, Clone)]
+struct Request {
+    method: String,
+    path: String,
+    cookie: String,
+    body: String,
+}
+
+/// Answers like the website does: the input of 2021 day 1 for the right session, and
+/// 7 is the only right answer
+fn respond(request: &Request) -> (u16, String) {
+    if request.cookie != format!("session={}", SESSION) {
+        return (
+            400,
+            "Puzzle inputs differ by user.  Please log in.".to_string(),
+        );
+    }
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/2021/day/1/input") => (200, INPUT.to_string()),
+        ("POST", "/2021/day/1/answer") => {
+            let message = match request.body.contains("answer=7") {
+                true => "That's the right answer! You are one gold star closer.",
+                false => "That's not the right answer; your answer is too low.",
+            };
+            (
+                200,
+                format!("<main><article><p>{}</p></article></main>", message),
+            )
+        }
+        _ => (404, "404 Not Found".to_string()),
+    }
+}
+
+/// Serves `respond` on a free port, keeping every request it gets
+fn serve() -> (String, Arc<Mutex<Vec<Request>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(vec![]));
+    let received = requests.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let mut parts = line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let path = parts.next().unwrap_or_default().to_string();
+            let (mut cookie, mut length) = (String::new(), 0);
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                let header = header.trim_end();
+                if header.is_empty() {
+                    break;
+                }
+                let (name, value) = header.split_once(": ").unwrap_or((header, ""));
+                match name.to_ascii_lowercase().as_str() {
+                    "cookie" => cookie = value.to_string(),
+                    "content-length" => length = value.parse().unwrap(),
+                    _ => {}
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            let request = Request {
+                method,
+                path,
+                cookie,
+                body: String::from_utf8(body).unwrap(),
+            };
+            let (status, body) = respond(&request);
+            received.lock().unwrap().push(request);
+            write!(
+                stream,
+                "HTTP/1.1 {} Stand-in\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    (url, requests)
+}
+
+/// An empty inputs directory of its own for each test
+fn inputs(name: &str) -> PathBuf {
+    let dir = format!("aoc2021-client-{}-{}", name, std::process::id());
+    let dir = std::env::temp_dir().join(dir);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    // Keeps the `.env` of the repository, if there is one, out of it
+    fs::write(dir.join(".env"), format!("AOC_INPUT={}\n", dir.display())).unwrap();
+    dir
+}
+
+fn run(dir: &Path, url: &str, session: Option<&str>, args: &[&str]) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_aoc2021"));
+    command
+        .args(args)
+        .current_dir(dir)
+        .env("AOC_INPUT", dir)
+        .env("AOC_BASE_URL", url)
+        .env("RUST_BACKTRACE", "0")
+        .env_remove("AOC_SESSION");
+    if let Some(session) = session {
+        command.env("AOC_SESSION", session);
+    }
+    command.output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{}", stderr(output));
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).to_string()
+}
+
+#[test]
+fn fetch_caches_the_input() {
+    let (url, requests) = serve();
+    let dir = inputs("fetch");
+
+    let output = stdout(&run(&dir, &url, Some(SESSION), &["fetch", "1"]));
+    assert!(output.contains("Saved"), "{}", output);
+    assert_eq!(fs::read_to_string(dir.join("Day1")).unwrap(), INPUT);
+
+    let output = stdout(&run(&dir, &url, Some(SESSION), &["fetch", "1"]));
+    assert!(output.contains("already cached"), "{}", output);
+    assert_eq!(requests.lock().unwrap().len(), 1);
+
+    // Running a day downloads its input too, if it's missing
+    fs::remove_file(dir.join("Day1")).unwrap();
+    let output = stdout(&run(&dir, &url, Some(SESSION), &["run", "1"]));
+    assert!(output.contains("Part1: 7"), "{}", output);
+    assert_eq!(requests.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn session_errors() {
+    let (url, requests) = serve();
+    let dir = inputs("session");
+
+    let output = run(&dir, &url, None, &["fetch", "1"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("AOC_SESSION is not set"));
+    assert!(requests.lock().unwrap().is_empty());
+
+    let output = run(&dir, &url, Some("expired"), &["fetch", "1"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("refused the session"));
+    assert!(!dir.join("Day1").exists());
+
+    let output = run(&dir, &url, Some(SESSION), &["fetch", "2"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("not found"));
+}
+
+#[test]
+fn submit_records_the_verdict() {
+    let (url, requests) = serve();
+    let dir = inputs("submit");
+
+    // Part 2 is 5, which the stand-in says is too low
+    let output = stdout(&run(&dir, &url, Some(SESSION), &["submit", "1", "-p", "2"]));
+    assert!(output.contains("too low"), "{}", output);
+    let output = stdout(&run(&dir, &url, Some(SESSION), &["submit", "1", "-p", "1"]));
+    assert!(output.contains("right answer"), "{}", output);
+
+    let history = fs::read_to_string(dir.join("history.toml")).unwrap();
+    assert!(
+        history.contains("\"too-low\"") && history.contains("\"right\""),
+        "{}",
+        history
+    );
+    let answers = fs::read_to_string(dir.join("answers.toml")).unwrap();
+    assert!(answers.contains("part1 = \"7\""), "{}", answers);
+
+    // Known verdicts aren't sent again
+    let output = run(&dir, &url, Some(SESSION), &["submit", "1", "-p", "1"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("already solved"));
+    let output = run(&dir, &url, Some(SESSION), &["submit", "1", "-p", "2"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("already wrong"));
+
+    let requests = requests.lock().unwrap();
+    let submitted = requests
+        .iter()
+        .filter(|r| r.method == "POST")
+        .collect::<Vec<_>>();
+    assert_eq!(submitted.len(), 2);
+    assert!(submitted[0].body.contains("level=2") && submitted[0].body.contains("answer=5"));
+}
@@ -0,0 +1,58 @@
+//! Runs the worked examples in `fixtures/` through every registered solution
+
+use aoc2021::fixture::Fixture;
+use aoc2021::registry;
+
+#[test]
+fn examples() {
+    let mut failures = vec![];
+    for registration in registry::all() {
+        let label = format!(
+            "{} day {} ({})",
+            registration.year, registration.day, registration.variant
+        );
+        let fixture = match Fixture::load(registration.year, registration.day).unwrap() {
+            Some(fixture) => fixture,
+            None if registration.code.implemented() => {
+                failures.push(format!("{}: no fixture", label));
+                continue;
+            }
+            None => continue,
+        };
+        for (i, example) in fixture.examples.iter().enumerate() {
+            let name = example.name.clone().unwrap_or_else(|| (i + 1).to_string());
+            let params = match example.params(registration.code.params()) {
+                Ok(params) => params,
+                Err(e) => {
+                    failures.push(format!("{} example {}: {}", label, name, e));
+                    continue;
+                }
+            };
+            for part in 1..=2 {
+                let expected = match example.expected(part) {
+                    Some(expected) => expected,
+                    None => continue,
+                };
+                let actual = match part {
+                    1 => registration.code.part1(example.input(), &params),
+                    _ => registration.code.part2(example.input(), &params),
+                };
+                match actual {
+                    Ok(actual) if actual.to_submission() == expected => {}
+                    Ok(actual) => failures.push(format!(
+                        "{} example {} part {}: expected {}, got {}",
+                        label,
+                        name,
+                        part,
+                        expected,
+                        actual.to_submission()
+                    )),
+                    Err(e) => {
+                        failures.push(format!("{} example {} part {}: {}", label, name, part, e))
+                    }
+                }
+            }
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}